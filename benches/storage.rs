@@ -0,0 +1,129 @@
+//! Compares the register storage backends on tight loops.
+//!
+//! Run with `cargo bench --bench storage`. Every case is warmed up, then timed
+//! `SAMPLES` times, and the median is compared with sparse storage.
+
+use std::hint::black_box;
+use std::io::{empty, sink, BufReader};
+use std::time::{Duration, Instant};
+
+use ramemu::program::Program;
+use ramemu::ram::{MemoryLimit, Ram};
+use ramemu::registers::{DenseStorage, Registers, Storage, StorageKind};
+
+const WARMUP: usize = 3;
+const SAMPLES: usize = 15;
+
+const KINDS: [(&str, StorageKind); 3] = [
+    ("sparse", StorageKind::Sparse),
+    ("dense", StorageKind::Dense),
+    (
+        "hybrid",
+        StorageKind::Hybrid(StorageKind::DEFAULT_DENSE_LEN),
+    ),
+];
+
+// Adds every register of a 256 register array to itself, 200 times.
+const ARRAY: &str = r#"
+        LOAD =200
+        STORE 3
+outer:  LOAD =256
+        STORE 1
+inner:  LOAD 1
+        ADD =9
+        STORE 2
+        LOAD *2
+        ADD 1
+        STORE *2
+        DEC 1
+        LOAD 1
+        JGTZ inner
+        DEC 3
+        LOAD 3
+        JGTZ outer
+        HALT
+"#;
+
+// Sums the numbers up to 1000 into R2, 200 times.
+const COUNTER: &str = r#"
+        LOAD =200
+        STORE 3
+outer:  LOAD =1000
+        STORE 1
+inner:  LOAD 2
+        ADD 1
+        STORE 2
+        DEC 1
+        LOAD 1
+        JGTZ inner
+        DEC 3
+        LOAD 3
+        JGTZ outer
+        HALT
+"#;
+
+/// Runs `routine` `WARMUP` times, then returns the median of `SAMPLES` timed runs.
+fn median(mut routine: impl FnMut() -> Duration) -> Duration {
+    for _ in 0..WARMUP {
+        routine();
+    }
+    let mut samples: Vec<_> = (0..SAMPLES).map(|_| routine()).collect();
+    samples.sort_unstable();
+    samples[SAMPLES / 2]
+}
+
+fn report(group: &str, results: &[(&str, Duration)]) {
+    println!("{group}");
+    let baseline = results[0].1.as_secs_f64();
+    for (name, time) in results {
+        let speedup = baseline / time.as_secs_f64();
+        println!("  {name:>14}: {time:>12.3?}  {speedup:.2}x");
+    }
+}
+
+/// Increments registers `0..256` in place, 2000 times.
+fn registers<S: Storage<i64>>(mut registers: Registers<i64, S>) -> Duration {
+    let start = Instant::now();
+    for _ in 0..2000 {
+        for index in 0..256 {
+            let value = registers.get(black_box(index));
+            registers.set(index, value + 1);
+        }
+    }
+    black_box(&registers);
+    start.elapsed()
+}
+
+fn ram(program: &Program, kind: StorageKind) -> Duration {
+    let mut ram = Ram::new(
+        program.clone(),
+        Box::new(BufReader::new(empty())),
+        Box::new(sink()),
+    )
+    .with_memory_limit(MemoryLimit::unlimited().with_max_index(1024))
+    .with_storage(kind);
+    let start = Instant::now();
+    ram.run().expect("Program runs");
+    let elapsed = start.elapsed();
+    black_box(ram.get_registers());
+    elapsed
+}
+
+fn main() {
+    let mut results: Vec<_> = KINDS
+        .iter()
+        .map(|&(name, kind)| (name, median(|| registers(Registers::new(kind)))))
+        .collect();
+    let dense = || registers(Registers::from_storage(DenseStorage::default()));
+    results.push(("dense (static)", median(dense)));
+    report("Registers::get and set", &results);
+
+    for (group, source) in [("Ram, array loop", ARRAY), ("Ram, counter loop", COUNTER)] {
+        let program = Program::from_source(source).expect("Program is correct");
+        let results: Vec<_> = KINDS
+            .iter()
+            .map(|&(name, kind)| (name, median(|| ram(&program, kind))))
+            .collect();
+        report(group, &results);
+    }
+}
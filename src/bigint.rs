@@ -0,0 +1,472 @@
+//! The `bigint` module provides [`BigInt`], an arbitrary-precision integer
+//! [`Word`] for programs whose values do not fit into `i64`, like factorials.
+//!
+//! Arithmetic never overflows. Register indices still have to fit into
+//! `usize`, so indirect addressing with a huge value is a segmentation fault.
+//!
+//! # Examples
+//!
+//! ```
+//! use ramemu::bigint::BigInt;
+//! use ramemu::program::Program;
+//! use ramemu::ram::Ram;
+//! use std::io::BufReader;
+//! use std::io::BufWriter;
+//!
+//! // 25!
+//! let source = "
+//!     load =1
+//!     store 1
+//!     load =25
+//!     store 2
+//! loop:
+//!     load 1
+//!     mult 2
+//!     store 1
+//!     load 2
+//!     sub =1
+//!     store 2
+//!     jgtz loop
+//!     halt
+//! ";
+//! let program = Program::from_source(source).unwrap();
+//! let reader = BufReader::new(std::io::empty());
+//! let writer = BufWriter::new(std::io::sink());
+//! let mut ram: Ram<BigInt> = Ram::from_program(program, Box::new(reader), Box::new(writer));
+//!
+//! ram.run().unwrap();
+//! assert_eq!(
+//!     ram.get_registers().get(1).to_string(),
+//!     "15511210043330985984000000"
+//! );
+//! ```
+//!
+//! [`Word`]: crate::word::Word
+
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::LowerHex;
+
+use crate::errors::InterpretErrorKind;
+use crate::word::Word;
+
+/// Base of the decimal chunks used for parsing and printing.
+const DECIMAL_BASE: u32 = 1_000_000_000;
+const DECIMAL_DIGITS: usize = 9;
+
+/// An arbitrary-precision signed integer.
+///
+/// The magnitude is stored as little-endian 32-bit limbs without trailing
+/// zeros, so zero has no limbs and is never negative.
+#[derive(Default, Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: Vec<u32>,
+}
+
+impl BigInt {
+    /// Returns `true` if the value is less than zero.
+    #[inline]
+    #[must_use]
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Returns the quotient rounded towards zero and the remainder, which has
+    /// the sign of `self`.
+    ///
+    /// If `rhs` is zero, returns `None`.
+    #[must_use]
+    pub fn div_rem(&self, rhs: &Self) -> Option<(Self, Self)> {
+        if rhs.magnitude.is_empty() {
+            return None;
+        }
+        let (quotient, remainder) = div_rem_magnitude(&self.magnitude, &rhs.magnitude);
+        Some((
+            Self::from_parts(self.negative != rhs.negative, quotient),
+            Self::from_parts(self.negative, remainder),
+        ))
+    }
+
+    fn from_parts(negative: bool, mut magnitude: Vec<u32>) -> Self {
+        trim(&mut magnitude);
+        Self {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+
+    fn add_signed(&self, rhs: &Self, rhs_negative: bool) -> Self {
+        if self.negative == rhs_negative {
+            return Self::from_parts(
+                self.negative,
+                add_magnitude(&self.magnitude, &rhs.magnitude),
+            );
+        }
+        match cmp_magnitude(&self.magnitude, &rhs.magnitude) {
+            Ordering::Less => {
+                Self::from_parts(rhs_negative, sub_magnitude(&rhs.magnitude, &self.magnitude))
+            }
+            _ => Self::from_parts(
+                self.negative,
+                sub_magnitude(&self.magnitude, &rhs.magnitude),
+            ),
+        }
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        let magnitude = value.unsigned_abs();
+        #[allow(clippy::cast_possible_truncation)]
+        Self::from_parts(value < 0, vec![magnitude as u32, (magnitude >> 32) as u32])
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.magnitude, &other.magnitude),
+            (true, true) => cmp_magnitude(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut chunks = Vec::new();
+        let mut magnitude = self.magnitude.clone();
+        while !magnitude.is_empty() {
+            let (quotient, remainder) = div_rem_small(&magnitude, DECIMAL_BASE);
+            chunks.push(remainder);
+            magnitude = quotient;
+        }
+
+        let mut text = String::new();
+        let mut chunks = chunks.iter().rev();
+        text.push_str(&chunks.next().copied().unwrap_or(0).to_string());
+        for chunk in chunks {
+            text.push_str(&format!("{chunk:0DECIMAL_DIGITS$}"));
+        }
+        f.pad_integral(!self.negative, "", &text)
+    }
+}
+
+impl LowerHex for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut limbs = self.magnitude.iter().rev();
+        let mut text = format!("{:x}", limbs.next().copied().unwrap_or(0));
+        for limb in limbs {
+            text.push_str(&format!("{limb:08x}"));
+        }
+        f.pad_integral(!self.negative, "0x", &text)
+    }
+}
+
+impl Debug for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Word for BigInt {
+    #[inline]
+    fn from_i64(value: i64) -> Option<Self> {
+        Some(value.into())
+    }
+
+    #[inline]
+    fn to_hex(&self) -> Option<String> {
+        Some(format!("{self:#x}"))
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        if self.magnitude.len() > 2 {
+            return None;
+        }
+        let magnitude = self
+            .magnitude
+            .iter()
+            .rev()
+            .fold(0u64, |acc, &limb| (acc << 32) | u64::from(limb));
+        if self.negative {
+            0i64.checked_sub_unsigned(magnitude)
+        } else {
+            i64::try_from(magnitude).ok()
+        }
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let mut magnitude = Vec::new();
+        let head = digits.len() % DECIMAL_DIGITS;
+        let chunks = std::iter::once(&digits[..head]).chain(
+            digits.as_bytes()[head..]
+                .chunks(DECIMAL_DIGITS)
+                .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default()),
+        );
+        for chunk in chunks.filter(|chunk| !chunk.is_empty()) {
+            let chunk: u32 = chunk.parse().ok()?;
+            mul_small_add(&mut magnitude, DECIMAL_BASE, chunk);
+        }
+        Some(Self::from_parts(negative, magnitude))
+    }
+
+    #[inline]
+    fn add(&self, rhs: &Self) -> Result<Self, InterpretErrorKind> {
+        Ok(self.add_signed(rhs, rhs.negative))
+    }
+
+    #[inline]
+    fn sub(&self, rhs: &Self) -> Result<Self, InterpretErrorKind> {
+        Ok(self.add_signed(rhs, !rhs.negative))
+    }
+
+    #[inline]
+    fn mul(&self, rhs: &Self) -> Result<Self, InterpretErrorKind> {
+        Ok(Self::from_parts(
+            self.negative != rhs.negative,
+            mul_magnitude(&self.magnitude, &rhs.magnitude),
+        ))
+    }
+
+    #[inline]
+    fn div(&self, rhs: &Self) -> Result<Self, InterpretErrorKind> {
+        self.div_rem(rhs)
+            .map(|(quotient, _)| quotient)
+            .ok_or(InterpretErrorKind::DivisionByZero)
+    }
+
+    #[inline]
+    fn neg(&self) -> Result<Self, InterpretErrorKind> {
+        Ok(Self::from_parts(!self.negative, self.magnitude.clone()))
+    }
+
+    #[inline]
+    fn abs(&self) -> Result<Self, InterpretErrorKind> {
+        Ok(Self::from_parts(false, self.magnitude.clone()))
+    }
+
+    #[inline]
+    fn floor(&self) -> Self {
+        self.clone()
+    }
+
+    #[inline]
+    fn trunc(&self) -> Self {
+        self.clone()
+    }
+}
+
+fn trim(magnitude: &mut Vec<u32>) {
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+}
+
+fn cmp_magnitude(lhs: &[u32], rhs: &[u32]) -> Ordering {
+    lhs.len()
+        .cmp(&rhs.len())
+        .then_with(|| lhs.iter().rev().cmp(rhs.iter().rev()))
+}
+
+fn add_magnitude(lhs: &[u32], rhs: &[u32]) -> Vec<u32> {
+    let (long, short) = if lhs.len() >= rhs.len() {
+        (lhs, rhs)
+    } else {
+        (rhs, lhs)
+    };
+    let mut result = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, &limb) in long.iter().enumerate() {
+        let sum = u64::from(limb) + u64::from(short.get(i).copied().unwrap_or(0)) + carry;
+        #[allow(clippy::cast_possible_truncation)]
+        result.push(sum as u32);
+        carry = sum >> 32;
+    }
+    if carry > 0 {
+        #[allow(clippy::cast_possible_truncation)]
+        result.push(carry as u32);
+    }
+    result
+}
+
+/// Subtracts magnitudes, `lhs` must not be less than `rhs`.
+fn sub_magnitude(lhs: &[u32], rhs: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(lhs.len());
+    let mut borrow = false;
+    for (i, &limb) in lhs.iter().enumerate() {
+        let (difference, overflow_rhs) = limb.overflowing_sub(rhs.get(i).copied().unwrap_or(0));
+        let (difference, overflow_borrow) = difference.overflowing_sub(u32::from(borrow));
+        result.push(difference);
+        borrow = overflow_rhs || overflow_borrow;
+    }
+    trim(&mut result);
+    result
+}
+
+fn mul_magnitude(lhs: &[u32], rhs: &[u32]) -> Vec<u32> {
+    let mut result = vec![0u32; lhs.len() + rhs.len()];
+    for (i, &a) in lhs.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &b) in rhs.iter().enumerate() {
+            let product = u64::from(a) * u64::from(b) + u64::from(result[i + j]) + carry;
+            #[allow(clippy::cast_possible_truncation)]
+            {
+                result[i + j] = product as u32;
+            }
+            carry = product >> 32;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            result[i + rhs.len()] = carry as u32;
+        }
+    }
+    trim(&mut result);
+    result
+}
+
+fn mul_small_add(magnitude: &mut Vec<u32>, factor: u32, addend: u32) {
+    let mut carry = u64::from(addend);
+    for limb in magnitude.iter_mut() {
+        let product = u64::from(*limb) * u64::from(factor) + carry;
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            *limb = product as u32;
+        }
+        carry = product >> 32;
+    }
+    if carry > 0 {
+        #[allow(clippy::cast_possible_truncation)]
+        magnitude.push(carry as u32);
+    }
+}
+
+fn div_rem_small(magnitude: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0u32; magnitude.len()];
+    let mut remainder = 0u64;
+    for (i, &limb) in magnitude.iter().enumerate().rev() {
+        let current = (remainder << 32) | u64::from(limb);
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            quotient[i] = (current / u64::from(divisor)) as u32;
+        }
+        remainder = current % u64::from(divisor);
+    }
+    trim(&mut quotient);
+    #[allow(clippy::cast_possible_truncation)]
+    (quotient, remainder as u32)
+}
+
+/// Divides magnitudes bit by bit, `rhs` must not be zero.
+fn div_rem_magnitude(lhs: &[u32], rhs: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if let [divisor] = rhs {
+        let (quotient, remainder) = div_rem_small(lhs, *divisor);
+        return (quotient, vec![remainder]);
+    }
+    if cmp_magnitude(lhs, rhs) == Ordering::Less {
+        return (Vec::new(), lhs.to_vec());
+    }
+
+    let mut quotient = vec![0u32; lhs.len()];
+    let mut remainder: Vec<u32> = Vec::with_capacity(rhs.len() + 1);
+    for bit in (0..lhs.len() * 32).rev() {
+        let mut carry = (lhs[bit / 32] >> (bit % 32)) & 1;
+        for limb in &mut remainder {
+            let next = *limb >> 31;
+            *limb = (*limb << 1) | carry;
+            carry = next;
+        }
+        if carry > 0 {
+            remainder.push(carry);
+        }
+        if cmp_magnitude(&remainder, rhs) != Ordering::Less {
+            remainder = sub_magnitude(&remainder, rhs);
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    trim(&mut quotient);
+    (quotient, remainder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(text: &str) -> BigInt {
+        BigInt::parse(text).unwrap()
+    }
+
+    #[test]
+    fn test_parse_display() {
+        for text in [
+            "0",
+            "7",
+            "-42",
+            "4294967296",
+            "-123456789012345678901234567890",
+        ] {
+            assert_eq!(big(text).to_string(), text);
+        }
+        assert_eq!(big("+0001").to_string(), "1");
+        assert_eq!(big("-0").to_string(), "0");
+        assert_eq!(BigInt::parse("1.5"), None);
+        assert_eq!(BigInt::parse("-"), None);
+    }
+
+    #[test]
+    fn test_lower_hex() {
+        assert_eq!(format!("{:#x}", big("0")), "0x0");
+        assert_eq!(format!("{:x}", big("4294967296")), "100000000");
+        assert_eq!(
+            format!("{:#x}", big("-36893488147419103231")),
+            "-0x1ffffffffffffffff"
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = big("123456789012345678901234567890");
+        let b = big("-987654321098765432109876543210");
+        assert_eq!(a.add(&b).unwrap(), big("-864197532086419753208641975320"));
+        assert_eq!(a.sub(&b).unwrap(), big("1111111110111111111011111111100"));
+        assert_eq!(
+            a.mul(&b).unwrap(),
+            big("-121932631137021795226185032733622923332237463801111263526900")
+        );
+        assert_eq!(b.div(&a).unwrap(), big("-8"));
+        assert_eq!(b.div_rem(&a).unwrap().1, big("-9000000000900000000090"));
+        assert_eq!(
+            a.div(&BigInt::default()),
+            Err(InterpretErrorKind::DivisionByZero)
+        );
+        assert!(b < a && b.abs().unwrap() > a);
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(BigInt::from(i64::MIN).to_i64(), Some(i64::MIN));
+        assert_eq!(BigInt::from(i64::MAX).to_i64(), Some(i64::MAX));
+        assert_eq!(big("9223372036854775808").to_i64(), None);
+        assert_eq!(big("-1").to_index(), None);
+        assert_eq!(big("18446744073709551616").to_index(), None);
+        assert_eq!(big("5").to_index(), Some(5));
+    }
+}
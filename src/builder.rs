@@ -0,0 +1,308 @@
+//! The `builder` module provides [`RamBuilder`], which configures a [`Ram`]
+//! in one place and validates the configuration before building it.
+//!
+//! # Examples
+//!
+//! ```
+//! use ramemu::builder::RamBuilder;
+//! use ramemu::program::Program;
+//! use ramemu::ram::{MemoryLimit, Tapes};
+//! use ramemu::registers::Registers;
+//! use ramemu::word::OverflowMode;
+//!
+//! let source = "
+//!     load =1
+//!     halt
+//! start:
+//!     load 1
+//!     mult 2
+//!     write 0
+//!     halt
+//! ";
+//! let program = Program::from_source(source).unwrap();
+//! let mut ram = RamBuilder::new(program)
+//!     .with_registers(Registers::from([0, 6, 7]))
+//!     .with_entry("start")
+//!     .with_tapes(Tapes::default())
+//!     .with_step_limit(100)
+//!     .with_memory_limit(MemoryLimit::unlimited().with_max_index(15))
+//!     .with_overflow_mode(OverflowMode::Saturating)
+//!     .build()
+//!     .unwrap();
+//!
+//! ram.run().unwrap();
+//! assert_eq!(ram.get_tapes().unwrap().output, [42]);
+//! ```
+
+use std::io::{BufRead, BufReader, Write};
+
+use crate::errors::BuildError;
+use crate::program::Program;
+use crate::ram::{MemoryLimit, Ram, Tapes};
+use crate::registers::Registers;
+use crate::word::{DivisionMode, OverflowMode, Word};
+
+/// Configures and validates a [`Ram`].
+///
+/// Without a reader, writer or tapes, the [`Ram`] reads from an empty input
+/// and discards its output.
+pub struct RamBuilder<T = i64> {
+    program: Program<T>,
+    registers: Registers<T>,
+    entry: Option<String>,
+    reader: Option<Box<dyn BufRead>>,
+    writer: Option<Box<dyn Write>>,
+    tapes: Option<Tapes<T>>,
+    step_limit: Option<u64>,
+    memory_limit: MemoryLimit,
+    overflow: OverflowMode,
+    division: DivisionMode,
+}
+
+impl<T: Word> RamBuilder<T> {
+    /// Creates a new [`RamBuilder`] for the given program.
+    #[must_use]
+    pub fn new(program: Program<T>) -> Self {
+        Self {
+            program,
+            registers: Registers::default(),
+            entry: None,
+            reader: None,
+            writer: None,
+            tapes: None,
+            step_limit: None,
+            memory_limit: MemoryLimit::unlimited(),
+            overflow: OverflowMode::default(),
+            division: DivisionMode::default(),
+        }
+    }
+
+    /// Sets the initial values of the registers. Registers that are not set
+    /// start out as zero.
+    ///
+    /// Every register set here counts against the memory limit, even if it
+    /// is set to zero.
+    #[inline]
+    #[must_use]
+    pub fn with_registers(mut self, registers: Registers<T>) -> Self {
+        self.registers = registers;
+        self
+    }
+
+    /// Starts the program at the given label instead of its first instruction.
+    #[inline]
+    #[must_use]
+    pub fn with_entry(mut self, label: impl Into<String>) -> Self {
+        self.entry = Some(label.into());
+        self
+    }
+
+    /// Sets the reader `READ` and `READC` consume.
+    #[inline]
+    #[must_use]
+    pub fn with_reader(mut self, reader: Box<dyn BufRead>) -> Self {
+        self.reader = Some(reader);
+        self
+    }
+
+    /// Sets the writer `WRITE` and `WRITEC` produce into.
+    #[inline]
+    #[must_use]
+    pub fn with_writer(mut self, writer: Box<dyn Write>) -> Self {
+        self.writer = Some(writer);
+        self
+    }
+
+    /// Uses tape I/O instead of a reader and writer, see [`Ram::with_tapes`].
+    #[inline]
+    #[must_use]
+    pub fn with_tapes(mut self, tapes: Tapes<T>) -> Self {
+        self.tapes = Some(tapes);
+        self
+    }
+
+    /// Limits the number of executed instructions, see [`Ram::with_step_limit`].
+    #[inline]
+    #[must_use]
+    pub fn with_step_limit(mut self, step_limit: u64) -> Self {
+        self.step_limit = Some(step_limit);
+        self
+    }
+
+    /// Limits the registers the program may use, see [`MemoryLimit`].
+    ///
+    /// The non-zero initial registers count as written registers.
+    #[inline]
+    #[must_use]
+    pub fn with_memory_limit(mut self, memory_limit: MemoryLimit) -> Self {
+        self.memory_limit = memory_limit;
+        self
+    }
+
+    /// Sets how arithmetic handles overflow, see [`Ram::with_overflow_mode`].
+    #[inline]
+    #[must_use]
+    pub fn with_overflow_mode(mut self, overflow: OverflowMode) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Sets how `DIV` rounds, see [`Ram::with_division_mode`].
+    #[inline]
+    #[must_use]
+    pub fn with_division_mode(mut self, division: DivisionMode) -> Self {
+        self.division = division;
+        self
+    }
+
+    /// Validates the configuration and builds the [`Ram`].
+    /// # Errors
+    /// Returns a [`BuildError`] if the entry label is not defined, the initial
+    /// registers do not fit into the memory limit, or tapes are combined with
+    /// a reader or writer.
+    pub fn build(self) -> Result<Ram<T>, BuildError> {
+        if self.tapes.is_some() && (self.reader.is_some() || self.writer.is_some()) {
+            return Err(BuildError::ConflictingIo);
+        }
+
+        let pc = match &self.entry {
+            Some(label) => self
+                .program
+                .find_label(label)
+                .ok_or_else(|| BuildError::UnknownEntryLabel(label.as_str().into()))?,
+            None => Default::default(),
+        };
+
+        let mut written: Vec<_> = self
+            .registers
+            .entries()
+            .map(|(index, _)| index)
+            .filter(|index| index.0 != 0)
+            .collect();
+        written.sort_unstable();
+        if let Some(max_index) = self.memory_limit.max_index() {
+            if let Some(index) = written.iter().find(|index| index.0 > max_index) {
+                return Err(BuildError::RegisterOutOfBounds(index.0));
+            }
+        }
+        if let Some(max_registers) = self.memory_limit.max_registers() {
+            if written.len() > max_registers {
+                return Err(BuildError::TooManyRegisters(written.len()));
+            }
+        }
+
+        let reader = self
+            .reader
+            .unwrap_or_else(|| Box::new(BufReader::new(std::io::empty())));
+        let writer = self.writer.unwrap_or_else(|| Box::new(std::io::sink()));
+        let mut ram = Ram::from_program(self.program, reader, writer)
+            .with_memory_limit(self.memory_limit)
+            .with_overflow_mode(self.overflow)
+            .with_division_mode(self.division);
+        if let Some(tapes) = self.tapes {
+            ram = ram.with_tapes(tapes);
+        }
+        if let Some(step_limit) = self.step_limit {
+            ram = ram.with_step_limit(step_limit);
+        }
+        ram.registers = self.registers;
+        ram.prefilled = false;
+        ram.touched.extend(written);
+        ram.pc = pc;
+        Ok(ram)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::{InterpretError, InterpretErrorKind};
+    use crate::registers::RegisterId;
+
+    fn program(source: &str) -> Program {
+        Program::from_source(source).unwrap()
+    }
+
+    #[test]
+    fn test_build() {
+        let mut ram = RamBuilder::new(program("load 3\ndiv =-2\nstore 4\nhalt"))
+            .with_registers([(RegisterId(3), 7)].into_iter().collect())
+            .with_division_mode(DivisionMode::Floor)
+            .build()
+            .unwrap();
+        ram.run().unwrap();
+        assert_eq!(ram.get_registers().get(4), -4);
+
+        let mut ram = RamBuilder::new(program("loop: jmp loop"))
+            .with_step_limit(10)
+            .build()
+            .unwrap();
+        assert_eq!(
+            ram.run(),
+            Err(InterpretError {
+                kind: InterpretErrorKind::StepLimitExceeded,
+                line: 1
+            })
+        );
+        assert_eq!(ram.get_clock(), 10);
+    }
+
+    #[test]
+    fn test_validation() {
+        let build = |builder: RamBuilder| builder.build().err();
+
+        let source = "load =1\nend: halt";
+        assert_eq!(
+            build(RamBuilder::new(program(source)).with_entry("start")),
+            Some(BuildError::UnknownEntryLabel("start".into()))
+        );
+        assert_eq!(
+            build(RamBuilder::new(program(source)).with_entry("end")),
+            None
+        );
+
+        let registers = Registers::from([5, 1, 0, 2]);
+        let limited = |limit| {
+            RamBuilder::new(program(source))
+                .with_registers(registers.clone())
+                .with_memory_limit(limit)
+        };
+        let limit = MemoryLimit::unlimited();
+        assert_eq!(build(limited(limit.with_max_index(3))), None);
+        assert_eq!(
+            build(limited(limit.with_max_index(2))),
+            Some(BuildError::RegisterOutOfBounds(3))
+        );
+        assert_eq!(build(limited(limit.with_max_registers(3))), None);
+        assert_eq!(
+            build(limited(limit.with_max_registers(2))),
+            Some(BuildError::TooManyRegisters(3))
+        );
+
+        let zero = RamBuilder::new(program(source))
+            .with_registers([(RegisterId(7), 0)].into_iter().collect())
+            .with_memory_limit(limit.with_max_index(3));
+        assert_eq!(build(zero), Some(BuildError::RegisterOutOfBounds(7)));
+
+        let builder = RamBuilder::new(program(source))
+            .with_tapes(Tapes::default())
+            .with_writer(Box::new(std::io::sink()));
+        assert_eq!(build(builder), Some(BuildError::ConflictingIo));
+    }
+
+    #[test]
+    fn test_memory_limit_counts_initial_registers() {
+        let mut ram = RamBuilder::new(program("store 3\nhalt"))
+            .with_registers(Registers::from([0, 1, 2]))
+            .with_memory_limit(MemoryLimit::unlimited().with_max_registers(2))
+            .build()
+            .unwrap();
+        assert_eq!(
+            ram.run(),
+            Err(InterpretError {
+                kind: InterpretErrorKind::SegmentationFault("3".into()),
+                line: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,345 @@
+//! The `cache` module simulates a set-associative cache in front of the
+//! registers, driven by a [`MemoryTrace`].
+//!
+//! Sizes are counted in registers: a cache of `size` registers is split into
+//! lines of `line_size` registers, which are grouped into sets of
+//! `associativity` lines. Register `i` lives in line `i / line_size`, which is
+//! cached in set `line % sets`. Every access, whatever its kind, looks up the
+//! line and loads it on a miss, evicting a line of the set chosen by the
+//! [`ReplacementPolicy`].
+//!
+//! # Examples
+//!
+//! ```
+//! use ramemu::cache::{Cache, CacheConfig, ReplacementPolicy};
+//! use ramemu::registers::RegisterId;
+//! use ramemu::trace::{Access, AccessKind, MemoryTrace};
+//!
+//! let trace = MemoryTrace::new();
+//! for register in [0, 1, 8, 0, 9] {
+//!     trace.record(Access::new(AccessKind::Read, RegisterId(register), register));
+//! }
+//!
+//! // Two lines of four registers in a direct-mapped cache.
+//! let config = CacheConfig::new(8, 4, 1).with_policy(ReplacementPolicy::Fifo);
+//! let report = Cache::simulate(config, &trace).unwrap();
+//!
+//! assert_eq!(report.total().hits(), 1);
+//! assert_eq!(report.total().misses(), 4);
+//! assert_eq!(report.line(0).unwrap().misses(), 2);
+//! ```
+
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::errors::CacheConfigError;
+use crate::trace::MemoryTrace;
+
+/// Selects the line of a full set that is evicted on a miss.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ReplacementPolicy {
+    /// Evicts the least recently used line.
+    #[default]
+    Lru,
+    /// Evicts the line that was loaded first.
+    Fifo,
+}
+
+/// The geometry and replacement policy of a [`Cache`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CacheConfig {
+    size: usize,
+    line_size: usize,
+    associativity: usize,
+    policy: ReplacementPolicy,
+}
+
+impl CacheConfig {
+    /// Creates an LRU cache configuration holding `size` registers in lines of
+    /// `line_size` registers, with `associativity` lines per set.
+    #[inline]
+    #[must_use]
+    pub fn new(size: usize, line_size: usize, associativity: usize) -> Self {
+        Self {
+            size,
+            line_size,
+            associativity,
+            policy: ReplacementPolicy::default(),
+        }
+    }
+
+    /// Sets the replacement policy.
+    #[inline]
+    #[must_use]
+    pub fn with_policy(mut self, policy: ReplacementPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns the number of registers the cache holds.
+    #[inline]
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of registers in a line.
+    #[inline]
+    #[must_use]
+    pub fn line_size(&self) -> usize {
+        self.line_size
+    }
+
+    /// Returns the number of lines in a set.
+    #[inline]
+    #[must_use]
+    pub fn associativity(&self) -> usize {
+        self.associativity
+    }
+
+    /// Returns the replacement policy.
+    #[inline]
+    #[must_use]
+    pub fn policy(&self) -> ReplacementPolicy {
+        self.policy
+    }
+
+    /// Returns the number of sets, `0` if the configuration is invalid.
+    #[inline]
+    #[must_use]
+    pub fn sets(&self) -> usize {
+        self.validate().unwrap_or_default()
+    }
+
+    /// Checks that the cache can be split into sets and returns their number.
+    /// # Errors
+    /// Returns a [`CacheConfigError`] if a size is `0`, the size of a set
+    /// overflows, or the size is not a multiple of the size of a set.
+    pub fn validate(&self) -> Result<usize, CacheConfigError> {
+        if self.size == 0 || self.line_size == 0 || self.associativity == 0 {
+            return Err(CacheConfigError::ZeroSize);
+        }
+        let set_size = self
+            .line_size
+            .checked_mul(self.associativity)
+            .ok_or(CacheConfigError::SetSizeOverflow)?;
+        if !self.size.is_multiple_of(set_size) {
+            return Err(CacheConfigError::UnevenSets);
+        }
+        Ok(self.size / set_size)
+    }
+}
+
+/// Hit and miss counts of a [`Cache`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CacheStats {
+    hits: u64,
+    misses: u64,
+}
+
+impl CacheStats {
+    /// Returns the number of accesses that hit the cache.
+    #[inline]
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns the number of accesses that missed the cache.
+    #[inline]
+    #[must_use]
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Returns the number of accesses.
+    #[inline]
+    #[must_use]
+    pub fn accesses(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    /// Returns the fraction of accesses that missed, `0.0` without accesses.
+    #[must_use]
+    pub fn miss_rate(&self) -> f64 {
+        if self.accesses() == 0 {
+            return 0.0;
+        }
+        self.misses as f64 / self.accesses() as f64
+    }
+
+    fn count(&mut self, hit: bool) {
+        if hit {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+    }
+}
+
+/// The hits and misses of a simulation, in total and per source line.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct CacheReport {
+    total: CacheStats,
+    lines: BTreeMap<usize, CacheStats>,
+}
+
+impl CacheReport {
+    /// Returns the statistics over all accesses.
+    #[inline]
+    #[must_use]
+    pub fn total(&self) -> CacheStats {
+        self.total
+    }
+
+    /// Returns the statistics of the accesses of a source line, if it made any.
+    #[inline]
+    #[must_use]
+    pub fn line(&self, line: usize) -> Option<CacheStats> {
+        self.lines.get(&line).copied()
+    }
+
+    /// Iterates over the source lines that made accesses, in ascending order.
+    pub fn lines(&self) -> impl Iterator<Item = (usize, CacheStats)> + '_ {
+        self.lines.iter().map(|(&line, &stats)| (line, stats))
+    }
+}
+
+/// A set-associative cache simulator.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    config: CacheConfig,
+    /// The cached lines of every set, the next one to evict first.
+    sets: Vec<VecDeque<usize>>,
+    report: CacheReport,
+}
+
+impl Cache {
+    /// Creates an empty cache.
+    /// # Errors
+    /// Returns a [`CacheConfigError`] if the configuration is invalid, see
+    /// [`CacheConfig::validate`].
+    pub fn new(config: CacheConfig) -> Result<Self, CacheConfigError> {
+        let sets = config.validate()?;
+        Ok(Self {
+            config,
+            sets: vec![VecDeque::with_capacity(config.associativity); sets],
+            report: CacheReport::default(),
+        })
+    }
+
+    /// Runs every access of the trace through a new cache and returns the report.
+    /// # Errors
+    /// Returns a [`CacheConfigError`] if the configuration is invalid, see
+    /// [`CacheConfig::validate`].
+    pub fn simulate(
+        config: CacheConfig,
+        trace: &MemoryTrace,
+    ) -> Result<CacheReport, CacheConfigError> {
+        let mut cache = Self::new(config)?;
+        for access in trace.accesses().iter() {
+            cache.access(access.register.0, access.line);
+        }
+        Ok(cache.report)
+    }
+
+    /// Accesses a register on behalf of a source line and returns `true` on a hit.
+    pub fn access(&mut self, register: usize, line: usize) -> bool {
+        let tag = register / self.config.line_size;
+        let sets = self.sets.len();
+        let set = &mut self.sets[tag % sets];
+
+        let hit = match set.iter().position(|&cached| cached == tag) {
+            Some(position) => {
+                if self.config.policy == ReplacementPolicy::Lru {
+                    set.remove(position);
+                    set.push_back(tag);
+                }
+                true
+            }
+            None => {
+                if set.len() == self.config.associativity {
+                    set.pop_front();
+                }
+                set.push_back(tag);
+                false
+            }
+        };
+
+        self.report.total.count(hit);
+        self.report.lines.entry(line).or_default().count(hit);
+        hit
+    }
+
+    /// Returns the configuration of the cache.
+    #[inline]
+    #[must_use]
+    pub fn config(&self) -> CacheConfig {
+        self.config
+    }
+
+    /// Returns the hits and misses so far.
+    #[inline]
+    #[must_use]
+    pub fn report(&self) -> &CacheReport {
+        &self.report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replacement_policies() {
+        // One set of two lines holding one register each.
+        let config = CacheConfig::new(2, 1, 2);
+        let pattern = [1, 2, 1, 3, 1];
+
+        let mut lru = Cache::new(config).unwrap();
+        let hits: Vec<_> = pattern.iter().map(|&r| lru.access(r, 0)).collect();
+        assert_eq!(hits, [false, false, true, false, true]);
+
+        let mut fifo = Cache::new(config.with_policy(ReplacementPolicy::Fifo)).unwrap();
+        let hits: Vec<_> = pattern.iter().map(|&r| fifo.access(r, 0)).collect();
+        assert_eq!(hits, [false, false, true, false, false]);
+    }
+
+    #[test]
+    fn test_lines_and_sets() {
+        // Two sets of one line holding four registers.
+        let mut cache = Cache::new(CacheConfig::new(8, 4, 1)).unwrap();
+        assert_eq!(cache.config().sets(), 2);
+
+        assert!(!cache.access(0, 1));
+        assert!(cache.access(3, 1));
+        assert!(!cache.access(4, 2));
+        assert!(cache.access(2, 2));
+        assert!(!cache.access(8, 3));
+        assert!(!cache.access(0, 3));
+
+        let report = cache.report();
+        assert_eq!(report.total().accesses(), 6);
+        assert_eq!(report.line(2).unwrap().miss_rate(), 0.5);
+        assert_eq!(report.line(3).unwrap().miss_rate(), 1.0);
+        assert_eq!(report.line(4), None);
+        assert_eq!(report.lines().count(), 3);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let error = |config| Cache::new(config).err();
+        assert_eq!(
+            error(CacheConfig::new(10, 4, 1)),
+            Some(CacheConfigError::UnevenSets)
+        );
+        assert_eq!(
+            error(CacheConfig::new(8, 0, 1)),
+            Some(CacheConfigError::ZeroSize)
+        );
+        assert_eq!(
+            error(CacheConfig::new(8, usize::MAX, 2)),
+            Some(CacheConfigError::SetSizeOverflow)
+        );
+        assert_eq!(CacheConfig::new(10, 4, 1).sets(), 0);
+    }
+}
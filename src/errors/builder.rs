@@ -0,0 +1,33 @@
+/// Represents the invalid configurations a [`RamBuilder`] rejects.
+///
+/// [`RamBuilder`]: crate::builder::RamBuilder
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BuildError {
+    /// Occurs when the entry label is not defined in the program.
+    UnknownEntryLabel(Box<str>),
+    /// Occurs when an initial register lies beyond the maximum index of the
+    /// memory limit, with the index of the register.
+    RegisterOutOfBounds(usize),
+    /// Occurs when more initial registers are written than the memory limit
+    /// allows, with the number of written registers.
+    TooManyRegisters(usize),
+    /// Occurs when both tapes and a reader or writer are configured.
+    ConflictingIo,
+}
+
+impl std::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownEntryLabel(label) => write!(f, "Unknown Entry Label: {label}"),
+            Self::RegisterOutOfBounds(index) => {
+                write!(f, "Register {index} is out of the memory limit")
+            }
+            Self::TooManyRegisters(count) => {
+                write!(f, "{count} registers exceed the memory limit")
+            }
+            Self::ConflictingIo => write!(f, "Tapes can not be combined with a reader or writer"),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
@@ -0,0 +1,29 @@
+/// Represents the invalid configurations a [`Cache`] rejects.
+///
+/// [`Cache`]: crate::cache::Cache
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CacheConfigError {
+    /// Occurs when the size, line size or associativity is zero.
+    ZeroSize,
+    /// Occurs when the line size times the associativity does not fit into a `usize`.
+    SetSizeOverflow,
+    /// Occurs when the size is not a multiple of the line size times the associativity.
+    UnevenSets,
+}
+
+impl std::fmt::Display for CacheConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ZeroSize => write!(f, "Cache sizes must be positive"),
+            Self::SetSizeOverflow => {
+                write!(f, "Line size times associativity is too large")
+            }
+            Self::UnevenSets => write!(
+                f,
+                "Cache size must be a multiple of the line size times the associativity"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CacheConfigError {}
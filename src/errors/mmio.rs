@@ -0,0 +1,25 @@
+/// Represents the invalid register ranges a [`Ram`] rejects for a device.
+///
+/// [`Ram`]: crate::ram::Ram
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DeviceMapError {
+    /// Occurs when the range contains no register.
+    EmptyRange,
+    /// Occurs when the range contains the accumulator `R0`.
+    AccumulatorMapped,
+    /// Occurs when the range overlaps the range of another device, with the
+    /// first register both devices would map.
+    Overlap(usize),
+}
+
+impl std::fmt::Display for DeviceMapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyRange => write!(f, "Device range is empty"),
+            Self::AccumulatorMapped => write!(f, "Register 0 can not be memory-mapped"),
+            Self::Overlap(index) => write!(f, "Register {index} is mapped to another device"),
+        }
+    }
+}
+
+impl std::error::Error for DeviceMapError {}
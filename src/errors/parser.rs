@@ -13,6 +13,13 @@ pub enum ParseErrorKind {
     ArgumentIsRequired,
     /// Occurs when an argument is not valid.
     ArgumentIsNotValid(InvalidArgument),
+    /// Occurs when an instruction or addressing mode is not allowed by the instruction set profile.
+    NotAllowed {
+        /// Name of the profile.
+        profile: String,
+        /// The forbidden instruction or addressing mode.
+        feature: String,
+    },
     /// Represents an unknown error that occurred at a specific index.
     UnknownError,
 }
@@ -41,6 +48,12 @@ pub enum InvalidArgument {
 
     /// Occurs when an argument is not valid.
     ArgumentIsNotValid,
+
+    /// Occurs when a string literal is not terminated or has an invalid escape sequence.
+    StringLiteralIsNotValid,
+
+    /// Occurs when a numeric literal can not be represented by the machine word.
+    ValueIsOutOfRange,
 }
 
 impl ParseErrorKind {
@@ -55,11 +68,21 @@ impl ParseErrorKind {
     pub(crate) fn not_valid_argument() -> Self {
         Self::ArgumentIsNotValid(InvalidArgument::ArgumentIsNotValid)
     }
+    /// Creates a new `ParseError` for the `StringLiteralIsNotValid` case.
+    #[inline]
+    pub(crate) fn string_literal_not_valid() -> Self {
+        Self::ArgumentIsNotValid(InvalidArgument::StringLiteralIsNotValid)
+    }
     /// Creates a new `ParseError` for the `ArgumentValueMustBeNumberic` case.
     #[inline]
     pub(crate) fn argument_value_must_be_numeric() -> Self {
         Self::ArgumentIsNotValid(InvalidArgument::ArgumentValueMustBeNumberic)
     }
+    /// Creates a new `ParseError` for the `ValueIsOutOfRange` case.
+    #[inline]
+    pub(crate) fn value_out_of_range() -> Self {
+        Self::ArgumentIsNotValid(InvalidArgument::ValueIsOutOfRange)
+    }
 }
 
 impl std::fmt::Display for ParseErrorKind {
@@ -70,6 +93,9 @@ impl std::fmt::Display for ParseErrorKind {
             Self::UnsupportedOpcode(opcode) => write!(f, "Unsupported Opcode: {opcode}"),
             Self::ArgumentIsRequired => write!(f, "Argument is required"),
             Self::ArgumentIsNotValid(arg) => write!(f, "Argument is not valid: {arg}"),
+            Self::NotAllowed { profile, feature } => {
+                write!(f, "{feature} is not allowed by profile {profile}")
+            }
             Self::UnknownError => todo!(),
         }
     }
@@ -83,6 +109,8 @@ impl std::fmt::Display for InvalidArgument {
             Self::ArgumentValueMustBeNumberic => write!(f, "Argument must be numeric"),
             Self::PureArgumentIsNotAllowed => write!(f, "Pure argument is not allowed"),
             Self::ArgumentIsNotValid => write!(f, "Argument is not valid"),
+            Self::StringLiteralIsNotValid => write!(f, "String literal is not valid"),
+            Self::ValueIsOutOfRange => write!(f, "Value does not fit into a machine word"),
         }
     }
 }
@@ -1,20 +1,64 @@
+use crate::stmt::Comparison;
+
 /// Represents various interpretation errors that may occur during program execution.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum InterpretErrorKind {
-    /// Occurs when attempting to access memory outside the allowed range.
-    SegmentationFault,
+    /// Occurs when attempting to access memory outside the allowed range,
+    /// with the offending address.
+    SegmentationFault(Box<str>),
     /// Occurs when a reference to an unknown label is encountered.
     UnknownLabel,
     /// Occurs when invalid input is provided during program execution.
     InvalidInput(Box<str>),
+    /// Occurs when a value is read but the input is exhausted.
+    EndOfInput,
     /// Occurs when an invalid literal value is encountered.
     InvalidLiteral,
     /// Occurs when a division by zero is attempted.
     DivisionByZero,
+    /// Occurs when the result of an arithmetic operation does not fit into a register.
+    Overflow,
     /// Occurs when there is an error writing to provided writer.
     IOError,
     /// Occurs when the program is halted but step was made.
     Halted,
+    /// Occurs when a custom instruction is not registered in the machine.
+    UnknownInstruction,
+    /// Occurs when a host call is not registered in the machine.
+    UnknownHostCall(Box<str>),
+    /// Occurs when a host call returns an error.
+    HostCallError(Box<str>),
+    /// Occurs when a word in memory does not encode an instruction in RASP mode.
+    IllegalInstruction(Box<str>),
+    /// Occurs when an instruction can not be encoded into memory for RASP mode.
+    NotEncodable,
+    /// Occurs when a value is not a valid character code in the current character encoding.
+    InvalidCharacter(Box<str>),
+    /// Occurs when the upper bound of `RAND` is not positive.
+    InvalidRange(Box<str>),
+    /// Occurs when the comparison of an `ASSERT` instruction does not hold.
+    AssertionFailed {
+        /// Value of the left operand.
+        lhs: Box<str>,
+        /// The comparison that failed.
+        comparison: Comparison,
+        /// Value of the right operand.
+        rhs: Box<str>,
+    },
+    /// Occurs when the result of a real arithmetic operation is not a number.
+    NotANumber,
+    /// Occurs when the result of a real arithmetic operation is infinite.
+    Infinity,
+    /// Occurs when a register that was never written is read in strict mode,
+    /// with the index of the register.
+    UninitializedRead(usize),
+    /// Occurs when the machine would execute more instructions than its step limit.
+    StepLimitExceeded,
+    /// Occurs when a memory-mapped device fails to handle a load or store.
+    DeviceError(Box<str>),
+    /// Occurs when the program counter moves past the last instruction of the
+    /// program without a `HALT`.
+    EndOfProgram,
 }
 
 /// Represents various interpretation errors that may occur during program execution.
@@ -43,13 +87,39 @@ impl std::fmt::Display for InterpretError {
 impl std::fmt::Display for InterpretErrorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            InterpretErrorKind::SegmentationFault => write!(f, "Segmentation Fault"),
+            InterpretErrorKind::SegmentationFault(address) => {
+                write!(f, "Segmentation Fault at address {address}")
+            }
             InterpretErrorKind::UnknownLabel => write!(f, "Unknown Label"),
             InterpretErrorKind::InvalidInput(input) => write!(f, "Invalid input: {input}"),
+            InterpretErrorKind::EndOfInput => write!(f, "End of Input"),
             InterpretErrorKind::InvalidLiteral => write!(f, "Invalid Literal"),
             InterpretErrorKind::DivisionByZero => write!(f, "Division by Zero"),
+            InterpretErrorKind::Overflow => write!(f, "Overflow"),
             InterpretErrorKind::IOError => write!(f, "IO Error"),
             InterpretErrorKind::Halted => write!(f, "Halted"),
+            InterpretErrorKind::UnknownInstruction => write!(f, "Unknown Instruction"),
+            InterpretErrorKind::UnknownHostCall(name) => write!(f, "Unknown Host Call: {name}"),
+            InterpretErrorKind::HostCallError(message) => write!(f, "Host Call Error: {message}"),
+            InterpretErrorKind::IllegalInstruction(word) => {
+                write!(f, "Illegal Instruction: {word}")
+            }
+            InterpretErrorKind::NotEncodable => write!(f, "Not Encodable"),
+            InterpretErrorKind::InvalidCharacter(code) => write!(f, "Invalid Character: {code}"),
+            InterpretErrorKind::InvalidRange(bound) => write!(f, "Invalid Range: {bound}"),
+            InterpretErrorKind::AssertionFailed {
+                lhs,
+                comparison,
+                rhs,
+            } => write!(f, "Assertion Failed: {lhs} {comparison} {rhs}"),
+            InterpretErrorKind::NotANumber => write!(f, "Not a Number"),
+            InterpretErrorKind::Infinity => write!(f, "Infinity"),
+            InterpretErrorKind::UninitializedRead(index) => {
+                write!(f, "Uninitialized Read of register {index}")
+            }
+            InterpretErrorKind::StepLimitExceeded => write!(f, "Step Limit Exceeded"),
+            InterpretErrorKind::DeviceError(message) => write!(f, "Device Error: {message}"),
+            InterpretErrorKind::EndOfProgram => write!(f, "End of Program"),
         }
     }
 }
@@ -0,0 +1,359 @@
+//! The `extension` module provides a way to add user-defined instructions to
+//! the RAM assembly language without patching [`Op`], the parser or [`Ram`].
+//!
+//! A custom instruction implements the [`Instruction`] trait, which declares
+//! its mnemonic and the kind of operand it takes. Instructions are collected
+//! into an [`Extensions`] registry, which is passed to the parser (so that the
+//! mnemonic is recognized) and to the [`Ram`] (so that it can be executed).
+//! While running, an instruction gets a [`Context`] which provides controlled
+//! access to the registers, program counter and I/O of the machine.
+//!
+//! # Examples
+//!
+//! ```
+//! use ramemu::errors::InterpretErrorKind;
+//! use ramemu::extension::{Context, Extensions, Instruction, Operand, OperandKind};
+//! use ramemu::parser::parse_with_extensions;
+//! use ramemu::ram::Ram;
+//! use std::io::BufReader;
+//! use std::io::BufWriter;
+//!
+//! /// `SWAP n` swaps the values of register `0` and register `n`.
+//! struct Swap;
+//!
+//! impl Instruction for Swap {
+//!     fn mnemonic(&self) -> &str {
+//!         "SWAP"
+//!     }
+//!
+//!     fn operand(&self) -> OperandKind {
+//!         OperandKind::Register
+//!     }
+//!
+//!     fn execute(&self, ctx: &mut Context, operand: Operand) -> Result<(), InterpretErrorKind> {
+//!         let Operand::Register(register) = operand else {
+//!             unreachable!("Parser checks operand kind");
+//!         };
+//!         let index = ctx.register(register)?;
+//!         let first = ctx.accumulator()?;
+//!         ctx.set_accumulator(ctx.get(index)?);
+//!         ctx.set(index, first)?;
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let mut extensions = Extensions::default();
+//! extensions.register(Swap);
+//!
+//! let program = parse_with_extensions("load =1\nswap 3\nhalt", &extensions).unwrap();
+//! let reader = BufReader::new(std::io::empty());
+//! let writer = BufWriter::new(std::io::sink());
+//! let mut ram =
+//!     Ram::new(program, Box::new(reader), Box::new(writer)).with_extensions(extensions);
+//!
+//! ram.run().unwrap();
+//! assert_eq!(ram.get_registers().get(0), 0);
+//! assert_eq!(ram.get_registers().get(3), 1);
+//! ```
+//!
+//! [`Op`]: crate::stmt::Op
+//! [`Ram`]: crate::ram::Ram
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::rc::Rc;
+
+use rustc_hash::FxHashMap as HashMap;
+
+use crate::errors::InterpretErrorKind;
+use crate::program::CodeAddress;
+use crate::program::LabelId;
+use crate::ram::Ram;
+use crate::registers::RegisterId;
+use crate::registers::Registers;
+use crate::stmt::RegisterValue;
+use crate::stmt::Value;
+use crate::word::Word;
+
+/// A user-defined instruction.
+///
+/// The parser uses [`Instruction::mnemonic`] and [`Instruction::operand`] to
+/// recognize the instruction in the source code, and the [`Ram`] calls
+/// [`Instruction::execute`] each time the instruction is evaluated.
+///
+/// An instruction is written for one [`Word`] type, `i64` by default.
+///
+/// [`Ram`]: crate::ram::Ram
+pub trait Instruction<T = i64> {
+    /// Returns the mnemonic of the instruction. Mnemonics are case-insensitive.
+    fn mnemonic(&self) -> &str;
+
+    /// Returns the kind of operand the instruction takes.
+    fn operand(&self) -> OperandKind;
+
+    /// Executes the instruction with the given operand.
+    ///
+    /// The operand is guaranteed to be of the kind returned by [`Instruction::operand`]
+    /// when the program was produced by the parser.
+    /// # Errors
+    /// Returns an [`InterpretErrorKind`] if the instruction fails. The [`Ram`]
+    /// attaches the current line to it.
+    ///
+    /// [`Ram`]: crate::ram::Ram
+    fn execute(&self, ctx: &mut Context<T>, operand: Operand<T>) -> Result<(), InterpretErrorKind>;
+}
+
+/// Represents a kind of operand a custom instruction takes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum OperandKind {
+    /// No operand, e.g. `HALT`.
+    None,
+    /// A value operand, e.g. `LOAD =5`, `LOAD 5` or `LOAD *5`.
+    Value,
+    /// A register operand, e.g. `STORE 5` or `STORE *5`.
+    Register,
+    /// A label operand, e.g. `JUMP loop`.
+    Label,
+}
+
+/// Represents an operand passed to a custom instruction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Operand<T = i64> {
+    /// No operand.
+    None,
+    /// A value operand.
+    Value(Value<T>),
+    /// A register operand.
+    Register(RegisterValue),
+    /// A label operand.
+    Label(LabelId),
+}
+
+impl<T> Operand<T> {
+    /// Returns the kind of the operand.
+    #[must_use]
+    pub fn kind(&self) -> OperandKind {
+        match self {
+            Self::None => OperandKind::None,
+            Self::Value(_) => OperandKind::Value,
+            Self::Register(_) => OperandKind::Register,
+            Self::Label(_) => OperandKind::Label,
+        }
+    }
+}
+
+/// Represents an id of a custom instruction in an [`Extensions`] registry.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct InstructionId(pub usize);
+
+impl From<usize> for InstructionId {
+    fn from(value: usize) -> Self {
+        Self(value)
+    }
+}
+
+/// A registry of custom instructions.
+///
+/// The same registry must be used for parsing and running a program, because
+/// parsed statements refer to instructions by their [`InstructionId`].
+#[derive(Clone)]
+pub struct Extensions<T = i64> {
+    instructions: Vec<Rc<dyn Instruction<T>>>,
+    mnemonics: HashMap<String, InstructionId>,
+}
+
+impl<T> Default for Extensions<T> {
+    fn default() -> Self {
+        Self {
+            instructions: Vec::new(),
+            mnemonics: HashMap::default(),
+        }
+    }
+}
+
+impl<T> Extensions<T> {
+    /// Registers a custom instruction and returns its id.
+    ///
+    /// If an instruction with the same mnemonic is already registered, the new
+    /// one shadows it for the parser.
+    pub fn register(&mut self, instruction: impl Instruction<T> + 'static) -> InstructionId {
+        let id = InstructionId(self.instructions.len());
+        self.mnemonics
+            .insert(instruction.mnemonic().to_uppercase(), id);
+        self.instructions.push(Rc::new(instruction));
+        id
+    }
+
+    /// Returns the instruction with the given id.
+    ///
+    /// If the id is unknown, returns `None`.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, id: impl Into<InstructionId>) -> Option<&Rc<dyn Instruction<T>>> {
+        self.instructions.get(id.into().0)
+    }
+
+    /// Looks up an instruction by its mnemonic.
+    ///
+    /// If no instruction has this mnemonic, returns `None`.
+    #[must_use]
+    pub fn lookup(&self, mnemonic: &str) -> Option<(InstructionId, &Rc<dyn Instruction<T>>)> {
+        let id = *self.mnemonics.get(&mnemonic.to_uppercase())?;
+        self.get(id).map(|instruction| (id, instruction))
+    }
+
+    /// Returns `true` if no instructions are registered.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+}
+
+impl<T> Debug for Extensions<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.instructions.iter().map(|i| i.mnemonic()))
+            .finish()
+    }
+}
+
+/// Gives a custom [`Instruction`] controlled access to the state of a [`Ram`].
+///
+/// [`Ram`]: crate::ram::Ram
+pub struct Context<'a, T = i64> {
+    ram: &'a mut Ram<T>,
+    next_pc: CodeAddress,
+}
+
+impl<'a, T: Word> Context<'a, T> {
+    #[inline]
+    pub(crate) fn new(ram: &'a mut Ram<T>, next_pc: CodeAddress) -> Self {
+        Self { ram, next_pc }
+    }
+
+    #[inline]
+    pub(crate) fn next_pc(&self) -> CodeAddress {
+        self.next_pc
+    }
+
+    /// Returns the line of the instruction being executed.
+    #[inline]
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.ram.line
+    }
+
+    /// Returns the address of the instruction being executed.
+    #[inline]
+    #[must_use]
+    pub fn pc(&self) -> CodeAddress {
+        self.ram.pc
+    }
+
+    /// Returns a reference to the registers.
+    #[inline]
+    #[must_use]
+    pub fn registers(&self) -> &Registers<T> {
+        &self.ram.registers
+    }
+
+    /// Returns the value of the register at the given index.
+    /// # Errors
+    /// Returns [`InterpretErrorKind::SegmentationFault`] if the register is
+    /// outside the [`MemoryLimit`], or [`InterpretErrorKind::UninitializedRead`]
+    /// if it was never written in strict mode.
+    ///
+    /// [`MemoryLimit`]: crate::ram::MemoryLimit
+    #[inline]
+    pub fn get(&self, index: impl Into<RegisterId>) -> Result<T, InterpretErrorKind> {
+        let index = self.ram.address(index.into().0).map_err(|e| e.kind)?;
+        self.ram.read(index).map_err(|e| e.kind)
+    }
+
+    /// Sets the value of the register at the given index.
+    /// # Errors
+    /// Returns [`InterpretErrorKind::SegmentationFault`] if the register is
+    /// outside the [`MemoryLimit`].
+    ///
+    /// [`MemoryLimit`]: crate::ram::MemoryLimit
+    #[inline]
+    pub fn set(
+        &mut self,
+        index: impl Into<RegisterId>,
+        value: T,
+    ) -> Result<(), InterpretErrorKind> {
+        let index = self.ram.address(index.into().0).map_err(|e| e.kind)?;
+        self.ram.store(index, value).map_err(|e| e.kind)
+    }
+
+    /// Returns the value of register `0`.
+    /// # Errors
+    /// Returns [`InterpretErrorKind::UninitializedRead`] if register `0` was
+    /// never written in strict mode.
+    #[inline]
+    pub fn accumulator(&self) -> Result<T, InterpretErrorKind> {
+        self.ram.first().map_err(|e| e.kind)
+    }
+
+    /// Sets the value of register `0`.
+    #[inline]
+    pub fn set_accumulator(&mut self, value: T) {
+        self.ram.set_first(value);
+    }
+
+    /// Resolves a [`Value`] operand the same way built-in instructions do.
+    /// # Errors
+    /// Returns an [`InterpretErrorKind`] if the value cannot be resolved.
+    #[inline]
+    pub fn value(&self, value: Value<T>) -> Result<T, InterpretErrorKind> {
+        self.ram.get_with_value(value).map_err(|e| e.kind)
+    }
+
+    /// Resolves a [`RegisterValue`] operand to the index of the target register.
+    /// # Errors
+    /// Returns an [`InterpretErrorKind`] if the register cannot be resolved.
+    #[inline]
+    pub fn register(&self, register: RegisterValue) -> Result<RegisterId, InterpretErrorKind> {
+        self.ram.register_index(register).map_err(|e| e.kind)
+    }
+
+    /// Continues execution at the given label after this instruction.
+    /// # Errors
+    /// Returns [`InterpretErrorKind::UnknownLabel`] if the label is unknown.
+    pub fn jump(&mut self, label: LabelId) -> Result<(), InterpretErrorKind> {
+        self.next_pc = self.ram.jump_target(label).map_err(|e| e.kind)?;
+        Ok(())
+    }
+
+    /// Halts the machine after this instruction.
+    #[inline]
+    pub fn halt(&mut self) {
+        self.ram.halt = true;
+    }
+
+    /// Reads the next value like `READ`, from the input tape in tape mode.
+    /// # Errors
+    /// Returns an [`InterpretErrorKind`] if the input is exhausted or invalid.
+    #[inline]
+    pub fn read_value(&mut self) -> Result<T, InterpretErrorKind> {
+        self.ram.read_number().map_err(|e| e.kind)
+    }
+
+    /// Writes a value like `WRITE`, following the output policy or appending
+    /// it to the output tape in tape mode.
+    /// # Errors
+    /// Returns [`InterpretErrorKind::IOError`] if the writer fails.
+    #[inline]
+    pub fn write_value(&mut self, value: T) -> Result<(), InterpretErrorKind> {
+        self.ram.write_value(value).map_err(|e| e.kind)
+    }
+
+    /// Writes text like `PRINT`, to the writer even in tape mode.
+    /// # Errors
+    /// Returns [`InterpretErrorKind::IOError`] if the writer fails.
+    #[inline]
+    pub fn write_text(&mut self, text: &str) -> Result<(), InterpretErrorKind> {
+        self.ram.write_bytes(text.as_bytes()).map_err(|e| e.kind)
+    }
+}
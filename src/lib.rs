@@ -4,52 +4,93 @@
 //!
 //! The library is organized into the following modules:
 //!
+//! - [`bigint`] for the arbitrary-precision integer register value.
+//! - [`builder`] for configuring and validating a RAM machine before running it.
+//! - [`cache`] for simulating a cache over a memory access trace.
 //! - [`errors`] for error types related to parsing and interpretation.
+//! - [`extension`] for adding user-defined instructions.
+//! - [`mmio`] for mapping registers to user-defined devices.
 //! - [`parser`] for parsing assembly code into an intermediate representation.
+//! - [`profile`] for restricting the instruction set a program may use.
 //! - [`program`] for representing and working with a program in memory.
 //! - [`ram`] for the RAM machine implementation and its execution logic.
+//! - [`random`] for the seeded pseudo-random number generator used by `RAND`.
+//! - [`rasp`] for encoding programs into memory for the stored-program mode.
 //! - [`registers`] for working with the RAM machine registers.
 //! - [`stmt`] for representing and working with assembly statements.
+//! - [`trace`] for recording the register accesses of the RAM machine.
+//! - [`word`] for the value types the RAM machine registers can hold.
 //!
 //! Additionally, the library will provide the following optional features:
 //!
 //! - `wasm`: Adds WebAssembly bindings for using the library in a WebAssembly environment.
 //! - `serde`: Adds serialization and deserialization support for the RAM machine state.
 //!
+//! [`bigint`]: bigint/index.html
+//! [`builder`]: builder/index.html
+//! [`cache`]: cache/index.html
 //! [`errors`]: errors/index.html
+//! [`extension`]: extension/index.html
+//! [`mmio`]: mmio/index.html
 //! [`parser`]: parser/index.html
+//! [`profile`]: profile/index.html
 //! [`program`]: program/index.html
 //! [`ram`]: ram/index.html
+//! [`random`]: random/index.html
+//! [`rasp`]: rasp/index.html
 //! [`registers`]: registers/index.html
 //! [`stmt`]: stmt/index.html
+//! [`trace`]: trace/index.html
+//! [`word`]: word/index.html
 
 #![warn(missing_docs)]
 
 pub mod errors {
-    //! The [`errors`] module provides error types for various parsing and interpretation errors
+    //! The `errors` module provides error types for various parsing and interpretation errors
     //! that may occur during the parsing, validation, and execution of a program.
     //!
     //! This module includes the following error types:
     //! - [`ParseError`] for parsing errors that may occur during parsing and validating input.
     //! - [`InterpretError`] for interpretation errors that may occur during program execution.
+    //! - [`BuildError`] for invalid configurations of a RAM machine builder.
+    //! - [`CacheConfigError`] for invalid configurations of the cache simulator.
+    //! - [`DeviceMapError`] for invalid register ranges of memory-mapped devices.
     //!
     //! It also includes error-related types:
     //! - [`InvalidArgument`] for representing various invalid argument errors.
     //!
     //! [`ParseError`]: enum.ParseError.html
     //! [`InterpretError`]: enum.InterpretError.html
+    //! [`BuildError`]: enum.BuildError.html
+    //! [`CacheConfigError`]: enum.CacheConfigError.html
+    //! [`DeviceMapError`]: enum.DeviceMapError.html
     //! [`InvalidArgument`]: enum.InvalidArgument.html
-    //! [`errors`]: errors/index.html
+    mod builder;
+    mod cache;
+    mod mmio;
     mod parser;
     mod ram;
 
+    pub use builder::*;
+    pub use cache::*;
+    pub use mmio::*;
     pub use parser::*;
     pub use ram::*;
 }
+pub mod bigint;
+pub mod builder;
+pub mod cache;
+pub mod extension;
+pub mod mmio;
 pub mod parser;
+pub mod profile;
 pub mod program;
 pub mod ram;
+pub mod random;
+pub mod rasp;
 pub mod registers;
 pub mod stmt;
+pub mod trace;
+pub mod word;
 
 // TODO: Serde feature
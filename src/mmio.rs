@@ -0,0 +1,107 @@
+//! The `mmio` module provides memory-mapped I/O: a [`Device`] registered on a
+//! [`Ram`] for a range of register addresses receives the loads and stores of
+//! those registers instead of the [`Registers`].
+//!
+//! Every instruction that reads or writes a register operand reaches the
+//! device, including both hops of indirect operands and `INC`/`DEC`, which
+//! read and then write, as do [`Context::get`] and [`Context::set`]. The
+//! accumulator `R0` can not be mapped, and RASP instruction fetches bypass
+//! devices.
+//!
+//! # Examples
+//!
+//! ```
+//! use ramemu::mmio;
+//! use ramemu::program::Program;
+//! use ramemu::ram::Ram;
+//! use std::cell::RefCell;
+//! use std::io::BufReader;
+//! use std::io::BufWriter;
+//! use std::rc::Rc;
+//!
+//! // A serial port at register 100 and a counting timer at register 101.
+//! let sent = Rc::new(RefCell::new(Vec::new()));
+//! let port = Rc::clone(&sent);
+//! let serial = mmio::from_fns(
+//!     |_| Err("serial port is write-only".to_string()),
+//!     move |_, value| {
+//!         port.borrow_mut().push(value);
+//!         Ok(())
+//!     },
+//! );
+//! let mut ticks = 0;
+//! let timer = mmio::from_fns(
+//!     move |_| {
+//!         ticks += 1;
+//!         Ok(ticks)
+//!     },
+//!     |_, _| Err("timer is read-only".to_string()),
+//! );
+//!
+//! let program = Program::from_source("load 101\nadd 101\nstore 100\nhalt").unwrap();
+//! let reader = BufReader::new(std::io::empty());
+//! let writer = BufWriter::new(std::io::sink());
+//! let mut ram = Ram::new(program, Box::new(reader), Box::new(writer))
+//!     .with_device(100..101, serial)
+//!     .and_then(|ram| ram.with_device(101..102, timer))
+//!     .unwrap();
+//!
+//! ram.run().unwrap();
+//! assert_eq!(*sent.borrow(), [3]);
+//! ```
+//!
+//! [`Ram`]: crate::ram::Ram
+//! [`Registers`]: crate::registers::Registers
+//! [`Context::get`]: crate::extension::Context::get
+//! [`Context::set`]: crate::extension::Context::set
+
+/// A memory-mapped device.
+///
+/// An `Err` message of a handler is reported as [`DeviceError`].
+///
+/// [`DeviceError`]: crate::errors::InterpretErrorKind::DeviceError
+pub trait Device<T> {
+    /// Handles a load of the register at the given address.
+    /// # Errors
+    /// Returns a message if the device can not be read.
+    fn read(&mut self, address: usize) -> Result<T, String>;
+
+    /// Handles a store of the value into the register at the given address.
+    /// # Errors
+    /// Returns a message if the device can not be written.
+    fn write(&mut self, address: usize, value: T) -> Result<(), String>;
+}
+
+/// A [`Device`] made of a read and a write handler, see [`from_fns`].
+#[derive(Clone)]
+pub struct FnDevice<R, W> {
+    read: R,
+    write: W,
+}
+
+/// Creates a [`Device`] that calls `read` for loads and `write` for stores.
+#[inline]
+#[must_use]
+pub fn from_fns<T, R, W>(read: R, write: W) -> FnDevice<R, W>
+where
+    R: FnMut(usize) -> Result<T, String>,
+    W: FnMut(usize, T) -> Result<(), String>,
+{
+    FnDevice { read, write }
+}
+
+impl<T, R, W> Device<T> for FnDevice<R, W>
+where
+    R: FnMut(usize) -> Result<T, String>,
+    W: FnMut(usize, T) -> Result<(), String>,
+{
+    #[inline]
+    fn read(&mut self, address: usize) -> Result<T, String> {
+        (self.read)(address)
+    }
+
+    #[inline]
+    fn write(&mut self, address: usize, value: T) -> Result<(), String> {
+        (self.write)(address, value)
+    }
+}
@@ -5,35 +5,81 @@
 
 use crate::errors::ParseError;
 use crate::errors::ParseErrorKind;
+use crate::extension::Extensions;
+use crate::extension::InstructionId;
+use crate::extension::Operand;
+use crate::extension::OperandKind;
+use crate::profile::Profile;
 use crate::program::CodeAddress;
+use crate::program::HostCallId;
 use crate::program::LabelId;
 use crate::program::Program;
+use crate::program::StringId;
+use crate::stmt::Comparison;
 use crate::stmt::Op;
 use rustc_hash::FxHashMap as HashMap;
 
 use crate::stmt::Op::{
-    Add, Div, Halt, Input, Jump, JumpGreatherZero, JumpIfZero, Load, Mult, Output, Store, Sub,
+    Abs, Add, Assert, Clock, Custom, Dec, Div, Floor, Halt, Inc, Input, InputChar, Jump,
+    JumpGreatherZero, JumpIfZero, Load, Mult, Neg, Output, OutputChar, Print, Rand, Store, Sub,
+    Sys, Trunc,
 };
 use crate::stmt::RegisterValue;
 use crate::stmt::Stmt;
 use crate::stmt::Value;
+use crate::word::Word;
 
 /// Parses the source code and returns an iterator over [`Result<Stmt, ParseError>`].
 ///
 /// This function processes each line of the source code, parsing it into a [`Stmt`] or
 /// a [`ParseError`] if an error occurs. It skips empty lines and comments.
+///
+/// Pure values are parsed as `i64`, use [`parse_with`] for other [`Word`] types.
 /// # Errors
 /// Returns all errors that occurred while parsing the source code.
 pub fn parse(source: &str) -> Result<Program, Vec<ParseError>> {
+    parse_with_extensions(source, &Extensions::default())
+}
+
+/// Parses the source code, recognizing custom instructions from the given [`Extensions`].
+///
+/// Built-in opcodes take precedence over custom mnemonics.
+/// # Errors
+/// Returns all errors that occurred while parsing the source code.
+pub fn parse_with_extensions<T: Word>(
+    source: &str,
+    extensions: &Extensions<T>,
+) -> Result<Program<T>, Vec<ParseError>> {
+    parse_with(source, extensions, &Profile::full())
+}
+
+/// Parses the source code, rejecting instructions that are not allowed by the given [`Profile`].
+/// # Errors
+/// Returns all errors that occurred while parsing the source code.
+pub fn parse_with_profile(source: &str, profile: &Profile) -> Result<Program, Vec<ParseError>> {
+    parse_with(source, &Extensions::default(), profile)
+}
+
+/// Parses the source code, recognizing custom instructions from the given [`Extensions`]
+/// and rejecting instructions that are not allowed by the given [`Profile`].
+///
+/// Pure values are parsed as `T`.
+/// # Errors
+/// Returns all errors that occurred while parsing the source code.
+pub fn parse_with<T: Word>(
+    source: &str,
+    extensions: &Extensions<T>,
+    profile: &Profile,
+) -> Result<Program<T>, Vec<ParseError>> {
     let mut label_to_address: HashMap<LabelId, CodeAddress> = HashMap::default();
     let mut errors = Vec::new();
     let mut instructions = Vec::new();
 
-    let mut label_ids: HashMap<String, LabelId> = HashMap::default();
+    let mut symbols = Symbols::default();
     let lines = source.lines().enumerate().map(|(i, l)| (i + 1, l));
 
     for (line, source) in lines {
-        let (op, label) = match parse_line(source, &mut label_ids) {
+        let (op, label) = match parse_line_with_extensions(source, &mut symbols, extensions) {
             Ok(ParsedLine { op, label }) => (op, label),
             Err(kind) => {
                 errors.push(ParseError { kind, line });
@@ -41,6 +87,11 @@ pub fn parse(source: &str) -> Result<Program, Vec<ParseError>> {
             }
         };
 
+        if let Some(Err(kind)) = op.as_ref().map(|op| profile.check(op)) {
+            errors.push(ParseError { kind, line });
+            continue;
+        }
+
         let labels_code_address = CodeAddress(instructions.len());
 
         if let Some(op) = op {
@@ -59,21 +110,43 @@ pub fn parse(source: &str) -> Result<Program, Vec<ParseError>> {
     Ok(Program {
         instructions,
         labels: label_to_address,
+        label_names: symbols.labels,
+        host_calls: symbols
+            .host_calls
+            .into_iter()
+            .map(|(name, id)| (id, name))
+            .collect(),
+        strings: symbols
+            .strings
+            .into_iter()
+            .map(|(text, id)| (id, text))
+            .collect(),
     })
 }
 
+/// Holds the names encountered while parsing and the ids assigned to them.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Symbols {
+    /// Label name -> Label id mapping.
+    pub labels: HashMap<String, LabelId>,
+    /// Host call name -> Host call id mapping.
+    pub host_calls: HashMap<String, HostCallId>,
+    /// String literal -> String id mapping.
+    pub strings: HashMap<String, StringId>,
+}
+
 /// Represents a parsed line of source code.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub struct ParsedLine {
+pub struct ParsedLine<T = i64> {
     /// Op code in the line
-    pub op: Option<Op>,
+    pub op: Option<Op<T>>,
     /// Label code in the line
     pub label: Option<LabelId>,
 }
 
-impl ParsedLine {
+impl<T> ParsedLine<T> {
     /// Returns a new [`ParsedLine`] with the given op code and label code.
-    fn new(op: Option<Op>, label: Option<LabelId>) -> Self {
+    fn new(op: Option<Op<T>>, label: Option<LabelId>) -> Self {
         Self { op, label }
     }
 }
@@ -84,15 +157,44 @@ impl ParsedLine {
 /// This function processes a single line of source code, returning `None` for empty lines
 /// or lines containing only comments. If the line contains an instruction or label, it returns
 /// a [`Stmt`] wrapped in a `Some`. In case of a parsing error, it returns a [`ParseError`]
+///
+/// `SYS` and string literals refer to their name or text by an id that only
+/// the [`Symbols`] of the parse can resolve, so use [`parse_line_with_extensions`]
+/// for them.
 /// # Errors
-/// Returns a [`ParseError`] if the line contains an invalid instruction or label.
+/// Returns a [`ParseError`] if the line contains an invalid instruction or label,
+/// or [`ParseErrorKind::UnsupportedSyntax`] for `SYS` and string literals.
 #[allow(clippy::implicit_hasher)]
 pub fn parse_line(
     source: &str,
     label_ids: &mut HashMap<String, LabelId>,
 ) -> Result<ParsedLine, ParseErrorKind> {
-    let (source, _) = source.split_once('#').unwrap_or((source, ""));
-    let source = source.trim();
+    let mut symbols = Symbols {
+        labels: std::mem::take(label_ids),
+        ..Default::default()
+    };
+    let result = parse_line_with_extensions(source, &mut symbols, &Extensions::<i64>::default());
+    *label_ids = symbols.labels;
+    match result {
+        Ok(ParsedLine {
+            op: Some(Sys(_) | Print(_)),
+            ..
+        }) => Err(ParseErrorKind::UnsupportedSyntax),
+        result => result,
+    }
+}
+
+/// Parses a single line of source code like [`parse_line`], recognizing custom
+/// instructions from the given [`Extensions`] and recording names in [`Symbols`].
+/// # Errors
+/// Returns a [`ParseError`] if the line contains an invalid instruction or label.
+pub fn parse_line_with_extensions<T: Word>(
+    source: &str,
+    symbols: &mut Symbols,
+    extensions: &Extensions<T>,
+) -> Result<ParsedLine<T>, ParseErrorKind> {
+    let label_ids = &mut symbols.labels;
+    let source = strip_comment(source).trim();
 
     let (source, label_id) = match parse_label(source) {
         (Ok(Some(label)), source) => {
@@ -104,6 +206,24 @@ pub fn parse_line(
         (Err(_), _) => return Err(ParseErrorKind::LabelIsNotValid),
     };
 
+    if let Some((head, literal)) = source.split_once('"') {
+        let opcode = head.trim().to_uppercase();
+        if !matches!(opcode.as_str(), "WRITE" | "OUTPUT" | "PRINT") {
+            return Err(ParseErrorKind::UnsupportedSyntax);
+        }
+        let text = parse_string_literal(literal)?;
+        let len = symbols.strings.len();
+        let id = *symbols.strings.entry(text).or_insert(StringId(len));
+        return Ok(ParsedLine::new(Some(Print(id)), label_id));
+    }
+
+    if let Some((head, tail)) = source.trim_start().split_once(char::is_whitespace) {
+        if head.eq_ignore_ascii_case("ASSERT") {
+            let op = parse_assert(tail)?;
+            return Ok(ParsedLine::new(Some(op), label_id));
+        }
+    }
+
     let mut facts = source.split_whitespace();
 
     let facts = (facts.next(), facts.next(), facts.next());
@@ -117,69 +237,61 @@ pub fn parse_line(
     let opcode = head.to_uppercase();
 
     let opcode = match opcode.as_str() {
-        "LOAD" | "ADD" | "SUB" | "MULT" | "MUL" | "DIV" | "WRITE" | "OUTPUT" => {
-            parse_with_value(&opcode, tail.ok_or(ParseErrorKind::ArgumentIsRequired)?)?
-        }
+        "LOAD" | "ADD" | "SUB" | "MULT" | "MUL" | "DIV" | "WRITE" | "OUTPUT" | "WRITEC"
+        | "RAND" => parse_with_value(&opcode, tail.ok_or(ParseErrorKind::ArgumentIsRequired)?)?,
         "JUMP" | "JMP" | "JZ" | "JZERO" | "JGZ" | "JGTZ" => parse_with_label_arg(
             &opcode,
             tail.ok_or(ParseErrorKind::ArgumentIsRequired)?,
             label_ids,
         )?,
-        "STORE" | "INPUT" | "READ" => {
+        "STORE" | "INPUT" | "READ" | "READC" | "INC" | "DEC" => {
             parse_with_register(&opcode, tail.ok_or(ParseErrorKind::ArgumentIsRequired)?)?
         }
+        "NEG" => Neg,
+        "ABS" => Abs,
+        "FLOOR" => Floor,
+        "TRUNC" | "INT" => Trunc,
+        "CLOCK" => Clock,
+        "ASSERT" => return Err(ParseErrorKind::ArgumentIsRequired),
+        "SYS" => parse_with_host_call(
+            tail.ok_or(ParseErrorKind::ArgumentIsRequired)?,
+            &mut symbols.host_calls,
+        )?,
+        "PRINT" => {
+            tail.ok_or(ParseErrorKind::ArgumentIsRequired)?;
+            return Err(ParseErrorKind::not_valid_argument());
+        }
         "HALT" => Halt,
-        _ => return Err(ParseErrorKind::UnsupportedOpcode(opcode)),
+        _ => match extensions.lookup(&opcode) {
+            Some((id, instruction)) => parse_custom(id, instruction.operand(), tail, label_ids)?,
+            None => return Err(ParseErrorKind::UnsupportedOpcode(opcode)),
+        },
     };
 
     Ok(ParsedLine::new(Some(opcode), label_id))
 }
 
-fn parse_with_register(opcode: &str, tail: &str) -> Result<Op, ParseErrorKind> {
-    let arg: RegisterValue = {
-        if let Some(tail) = tail.strip_prefix('*') {
-            RegisterValue::Indirect(
-                tail.parse()
-                    .map_err(|_| ParseErrorKind::argument_value_must_be_numeric())?,
-            )
-        } else if let Ok(arg) = tail.parse::<usize>() {
-            RegisterValue::Direct(arg)
-        } else if tail.starts_with('=') {
-            return Err(ParseErrorKind::pure_argument_not_allowed());
-        } else {
-            return Err(ParseErrorKind::not_valid_argument());
-        }
-    };
+fn parse_with_register<T>(opcode: &str, tail: &str) -> Result<Op<T>, ParseErrorKind> {
+    let arg = parse_register(tail)?;
 
     Ok(match opcode {
         "STORE" => Store(arg),
         "INPUT" | "READ" => Input(arg),
+        "READC" => InputChar(arg),
+        "INC" => Inc(arg),
+        "DEC" => Dec(arg),
         _ => unreachable!("Opcodes were changed in parse function, but not there"),
     })
 }
 
-fn parse_with_value(head: &str, tail: &str) -> Result<Op, ParseErrorKind> {
-    let arg: Value = {
-        if let Some(tail) = tail.strip_prefix('=') {
-            Value::Pure(
-                tail.parse()
-                    .map_err(|_| ParseErrorKind::argument_value_must_be_numeric())?,
-            )
-        } else if let Some(tail) = tail.strip_prefix('*') {
-            Value::Register(RegisterValue::Indirect(
-                tail.parse()
-                    .map_err(|_| ParseErrorKind::argument_value_must_be_numeric())?,
-            ))
-        } else if let Ok(arg) = tail.parse::<usize>() {
-            Value::Register(RegisterValue::Direct(arg))
-        } else {
-            return Err(ParseErrorKind::not_valid_argument());
-        }
-    };
+fn parse_with_value<T: Word>(head: &str, tail: &str) -> Result<Op<T>, ParseErrorKind> {
+    let arg = parse_value(tail)?;
 
     Ok(match head {
         "LOAD" => Load(arg),
         "OUTPUT" | "WRITE" => Output(arg),
+        "WRITEC" => OutputChar(arg),
+        "RAND" => Rand(arg),
         "ADD" => Add(arg),
         "SUB" => Sub(arg),
         "MUL" | "MULT" => Mult(arg),
@@ -188,18 +300,12 @@ fn parse_with_value(head: &str, tail: &str) -> Result<Op, ParseErrorKind> {
     })
 }
 
-fn parse_with_label_arg(
+fn parse_with_label_arg<T>(
     head: &str,
     tail: &str,
     label_ids: &mut HashMap<String, LabelId>,
-) -> Result<Op, ParseErrorKind> {
-    let label: LabelId = if is_valid_label(tail) {
-        let label = tail;
-        let len = label_ids.len();
-        *label_ids.entry(label.to_string()).or_insert(LabelId(len))
-    } else {
-        return Err(ParseErrorKind::LabelIsNotValid);
-    };
+) -> Result<Op<T>, ParseErrorKind> {
+    let label = parse_label_arg(tail, label_ids)?;
 
     Ok(match head {
         "JUMP" | "JMP" => Jump(label),
@@ -209,10 +315,196 @@ fn parse_with_label_arg(
     })
 }
 
+fn parse_with_host_call<T>(
+    tail: &str,
+    host_call_ids: &mut HashMap<String, HostCallId>,
+) -> Result<Op<T>, ParseErrorKind> {
+    if !is_valid_label(tail) {
+        return Err(ParseErrorKind::not_valid_argument());
+    }
+    let len = host_call_ids.len();
+    let id = *host_call_ids
+        .entry(tail.to_string())
+        .or_insert(HostCallId(len));
+    Ok(Sys(id))
+}
+
+/// Parses the operands of `ASSERT`: two values separated by a comparison,
+/// e.g. `1 > =0`. A register may also be written as `R1`.
+fn parse_assert<T: Word>(tail: &str) -> Result<Op<T>, ParseErrorKind> {
+    let mut facts = tail.split_whitespace();
+    let (Some(lhs), Some(comparison), Some(rhs), None) =
+        (facts.next(), facts.next(), facts.next(), facts.next())
+    else {
+        return Err(ParseErrorKind::UnsupportedSyntax);
+    };
+
+    let comparison = match comparison {
+        "==" => Comparison::Equal,
+        "!=" => Comparison::NotEqual,
+        "<" => Comparison::Less,
+        "<=" => Comparison::LessOrEqual,
+        ">" => Comparison::Greater,
+        ">=" => Comparison::GreaterOrEqual,
+        _ => return Err(ParseErrorKind::not_valid_argument()),
+    };
+
+    let operand = |fact: &str| match fact.strip_prefix(['R', 'r']) {
+        Some(index) => index
+            .parse()
+            .map(|index| Value::Register(RegisterValue::Direct(index)))
+            .map_err(|_| ParseErrorKind::argument_value_must_be_numeric()),
+        None => parse_value(fact),
+    };
+
+    Ok(Assert(operand(lhs)?, comparison, operand(rhs)?))
+}
+
+fn parse_custom<T: Word>(
+    id: InstructionId,
+    kind: OperandKind,
+    tail: Option<&str>,
+    label_ids: &mut HashMap<String, LabelId>,
+) -> Result<Op<T>, ParseErrorKind> {
+    let operand = match (kind, tail) {
+        (OperandKind::None, None) => Operand::None,
+        (OperandKind::None, Some(_)) => return Err(ParseErrorKind::UnsupportedSyntax),
+        (_, None) => return Err(ParseErrorKind::ArgumentIsRequired),
+        (OperandKind::Value, Some(tail)) => Operand::Value(parse_value(tail)?),
+        (OperandKind::Register, Some(tail)) => Operand::Register(parse_register(tail)?),
+        (OperandKind::Label, Some(tail)) => Operand::Label(parse_label_arg(tail, label_ids)?),
+    };
+
+    Ok(Custom(id, operand))
+}
+
+fn parse_register(tail: &str) -> Result<RegisterValue, ParseErrorKind> {
+    if let Some(tail) = tail.strip_prefix('*') {
+        Ok(RegisterValue::Indirect(tail.parse().map_err(|_| {
+            ParseErrorKind::argument_value_must_be_numeric()
+        })?))
+    } else if let Ok(arg) = tail.parse::<usize>() {
+        Ok(RegisterValue::Direct(arg))
+    } else if tail.starts_with('=') {
+        Err(ParseErrorKind::pure_argument_not_allowed())
+    } else {
+        Err(ParseErrorKind::not_valid_argument())
+    }
+}
+
+fn parse_value<T: Word>(tail: &str) -> Result<Value<T>, ParseErrorKind> {
+    if let Some(tail) = tail.strip_prefix('=') {
+        if !is_numeric_literal(tail) {
+            return Err(ParseErrorKind::argument_value_must_be_numeric());
+        }
+        T::parse(tail)
+            .map(Value::Pure)
+            .ok_or(ParseErrorKind::value_out_of_range())
+    } else if let Some(tail) = tail.strip_prefix('*') {
+        Ok(Value::Register(RegisterValue::Indirect(
+            tail.parse()
+                .map_err(|_| ParseErrorKind::argument_value_must_be_numeric())?,
+        )))
+    } else if let Ok(arg) = tail.parse::<usize>() {
+        Ok(Value::Register(RegisterValue::Direct(arg)))
+    } else {
+        Err(ParseErrorKind::not_valid_argument())
+    }
+}
+
+/// Checks that the literal is an optionally signed decimal number, e.g. `-2.5`.
+fn is_numeric_literal(literal: &str) -> bool {
+    let digits = literal.strip_prefix(['-', '+']).unwrap_or(literal);
+    let is_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    match digits.split_once('.') {
+        Some((integer, fraction)) => is_digits(integer) && is_digits(fraction),
+        None => is_digits(digits),
+    }
+}
+
+fn parse_label_arg(
+    tail: &str,
+    label_ids: &mut HashMap<String, LabelId>,
+) -> Result<LabelId, ParseErrorKind> {
+    if !is_valid_label(tail) {
+        return Err(ParseErrorKind::LabelIsNotValid);
+    }
+    let len = label_ids.len();
+    Ok(*label_ids.entry(tail.to_string()).or_insert(LabelId(len)))
+}
+
+/// Parses the rest of a string literal after the opening quote, resolving
+/// escape sequences: `\n`, `\r`, `\t`, `\0`, `\\`, `\"` and `\u{XXXX}`.
+fn parse_string_literal(literal: &str) -> Result<String, ParseErrorKind> {
+    let mut text = String::new();
+    let mut chars = literal.chars();
+
+    loop {
+        match chars.next() {
+            Some('"') => break,
+            Some('\\') => text.push(parse_escape(&mut chars)?),
+            Some(c) => text.push(c),
+            None => return Err(ParseErrorKind::string_literal_not_valid()),
+        }
+    }
+
+    if !chars.as_str().trim().is_empty() {
+        return Err(ParseErrorKind::UnsupportedSyntax);
+    }
+    Ok(text)
+}
+
+fn parse_escape(chars: &mut std::str::Chars) -> Result<char, ParseErrorKind> {
+    Ok(match chars.next() {
+        Some('n') => '\n',
+        Some('r') => '\r',
+        Some('t') => '\t',
+        Some('0') => '\0',
+        Some('\\') => '\\',
+        Some('"') => '"',
+        Some('u') => {
+            let rest = chars
+                .as_str()
+                .strip_prefix('{')
+                .ok_or(ParseErrorKind::string_literal_not_valid())?;
+            let (code, _) = rest
+                .split_once('}')
+                .ok_or(ParseErrorKind::string_literal_not_valid())?;
+            let c = u32::from_str_radix(code, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or(ParseErrorKind::string_literal_not_valid())?;
+            chars.nth(code.len() + 1);
+            c
+        }
+        _ => return Err(ParseErrorKind::string_literal_not_valid()),
+    })
+}
+
+/// Removes the comment from the line, ignoring `#` inside string literals.
+fn strip_comment(source: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in source.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &source[..i],
+            _ => {}
+        }
+    }
+    source
+}
+
 fn parse_label(source: &str) -> (Result<Option<&str>, ParseErrorKind>, &str) {
-    match source.split_once(':') {
-        Some((label, tail)) if is_valid_label(label) => (Ok(Some(label)), tail),
-        Some((_, tail)) => (Err(ParseErrorKind::LabelIsNotValid), tail),
+    let head = source.find('"').map_or(source, |quote| &source[..quote]);
+    match head.split_once(':') {
+        Some((label, _)) if is_valid_label(label) => (Ok(Some(label)), &source[label.len() + 1..]),
+        Some((label, _)) => (
+            Err(ParseErrorKind::LabelIsNotValid),
+            &source[label.len() + 1..],
+        ),
         None => (Ok(None), source),
     }
 }
@@ -238,6 +530,10 @@ mod tests {
     use super::*;
     use crate::stmt::{RegisterValue, Value};
 
+    fn parse_as<T: Word>(source: &str) -> Result<Program<T>, Vec<ParseError>> {
+        parse_with(source, &Extensions::default(), &Profile::full())
+    }
+
     #[test]
     fn test_parse_line_load() {
         let mut label_ids = HashMap::default();
@@ -276,6 +572,226 @@ mod tests {
         assert_eq!(res.label, None);
     }
 
+    #[test]
+    fn test_parse_line_unary() {
+        let mut label_ids = HashMap::default();
+        let res = parse_line("INC 3", &mut label_ids).unwrap();
+        assert_eq!(res.op, Some(Inc(RegisterValue::Direct(3))));
+
+        let res = parse_line("dec *2", &mut label_ids).unwrap();
+        assert_eq!(res.op, Some(Dec(RegisterValue::Indirect(2))));
+
+        let res = parse_line("NEG", &mut label_ids).unwrap();
+        assert_eq!(res.op, Some(Neg));
+
+        let res = parse_line("abs", &mut label_ids).unwrap();
+        assert_eq!(res.op, Some(Abs));
+
+        let error = parse_line("INC =1", &mut label_ids).unwrap_err();
+        assert_eq!(error, ParseErrorKind::pure_argument_not_allowed());
+    }
+
+    #[test]
+    fn test_parse_line_custom() {
+        use crate::errors::InterpretErrorKind;
+        use crate::extension::{Context, Instruction};
+
+        struct Nop;
+
+        impl Instruction for Nop {
+            fn mnemonic(&self) -> &str {
+                "nop"
+            }
+
+            fn operand(&self) -> OperandKind {
+                OperandKind::None
+            }
+
+            fn execute(&self, _: &mut Context, _: Operand) -> Result<(), InterpretErrorKind> {
+                Ok(())
+            }
+        }
+
+        let mut extensions = Extensions::default();
+        let id = extensions.register(Nop);
+        let mut symbols = Symbols::default();
+
+        let res = parse_line_with_extensions("NOP", &mut symbols, &extensions).unwrap();
+        assert_eq!(res.op, Some(Custom(id, Operand::None)));
+
+        let error = parse_line_with_extensions("NOP 1", &mut symbols, &extensions).unwrap_err();
+        assert_eq!(error, ParseErrorKind::UnsupportedSyntax);
+
+        let error = parse_line("NOP", &mut symbols.labels).unwrap_err();
+        assert_eq!(error, ParseErrorKind::UnsupportedOpcode("NOP".to_string()));
+    }
+
+    #[test]
+    fn test_parse_line_char_io() {
+        let mut label_ids = HashMap::default();
+        let res = parse_line("READC *1", &mut label_ids).unwrap();
+        assert_eq!(res.op, Some(InputChar(RegisterValue::Indirect(1))));
+
+        let res = parse_line("writec =65", &mut label_ids).unwrap();
+        assert_eq!(res.op, Some(OutputChar(Value::Pure(65))));
+    }
+
+    #[test]
+    fn test_parse_line_rand() {
+        let mut label_ids = HashMap::default();
+        let res = parse_line("RAND =6", &mut label_ids).unwrap();
+        assert_eq!(res.op, Some(Rand(Value::Pure(6))));
+
+        let res = parse_line("clock", &mut label_ids).unwrap();
+        assert_eq!(res.op, Some(Clock));
+    }
+
+    #[test]
+    fn test_parse_decimal_literal() {
+        let program = parse_as::<f64>("load =2.5\nadd =2.5\nsub =-1\nfloor\nint").unwrap();
+        assert_eq!(program.instructions[0].op, Load(Value::Pure(2.5)));
+        assert_eq!(program.instructions[1].op, Add(Value::Pure(2.5)));
+        assert_eq!(program.instructions[2].op, Sub(Value::Pure(-1.0)));
+        assert_eq!(program.instructions[3].op, Floor);
+        assert_eq!(program.instructions[4].op, Trunc);
+
+        let mut label_ids = HashMap::default();
+        let error = parse_line("LOAD =2.", &mut label_ids).unwrap_err();
+        assert_eq!(error, ParseErrorKind::argument_value_must_be_numeric());
+        let error = parse_line("LOAD =2.5", &mut label_ids).unwrap_err();
+        assert_eq!(error, ParseErrorKind::value_out_of_range());
+    }
+
+    #[test]
+    fn test_parse_word_range() {
+        let errors = parse_as::<i32>(
+            "load =2147483647
+add =2147483648",
+        )
+        .unwrap_err();
+        assert_eq!(
+            errors,
+            vec![ParseError {
+                kind: ParseErrorKind::value_out_of_range(),
+                line: 2
+            }]
+        );
+        let program = parse_as::<u64>("load =18446744073709551615").unwrap();
+        assert_eq!(program.instructions[0].op, Load(Value::Pure(u64::MAX)));
+        assert!(parse_as::<u64>("load =-1").is_err());
+    }
+
+    #[test]
+    fn test_parse_line_assert() {
+        let mut label_ids = HashMap::default();
+        let res = parse_line("ASSERT 1 > =0", &mut label_ids).unwrap();
+        assert_eq!(
+            res.op,
+            Some(Assert(
+                Value::Register(RegisterValue::Direct(1)),
+                Comparison::Greater,
+                Value::Pure(0)
+            ))
+        );
+
+        let res = parse_line("check: assert R0 == *2 # invariant", &mut label_ids).unwrap();
+        assert_eq!(
+            res.op,
+            Some(Assert(
+                Value::Register(RegisterValue::Direct(0)),
+                Comparison::Equal,
+                Value::Register(RegisterValue::Indirect(2))
+            ))
+        );
+
+        let error = parse_line("ASSERT 1 <> 2", &mut label_ids).unwrap_err();
+        assert_eq!(error, ParseErrorKind::not_valid_argument());
+
+        let error = parse_line("ASSERT 1 ==", &mut label_ids).unwrap_err();
+        assert_eq!(error, ParseErrorKind::UnsupportedSyntax);
+
+        let error = parse_line("ASSERT", &mut label_ids).unwrap_err();
+        assert_eq!(error, ParseErrorKind::ArgumentIsRequired);
+    }
+
+    #[test]
+    fn test_parse_string_literal() {
+        let source = "start: write \"a: b # c\\n\" # comment\nprint \"\\u{1F600}\\t\\\"\\\\\"";
+        let program = parse(source).unwrap();
+        let texts: Vec<_> = program
+            .instructions
+            .iter()
+            .map(|stmt| match stmt.op {
+                Print(id) => program.decode_string(id).unwrap(),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(texts, vec!["a: b # c\n", "\u{1F600}\t\"\\"]);
+        assert_eq!(program.decode_label(0), Some(CodeAddress(0)));
+
+        let mut label_ids = HashMap::default();
+        let error = parse_line("print \"abc", &mut label_ids).unwrap_err();
+        assert_eq!(error, ParseErrorKind::string_literal_not_valid());
+        let error = parse_line("print \"\\q\"", &mut label_ids).unwrap_err();
+        assert_eq!(error, ParseErrorKind::string_literal_not_valid());
+        let error = parse_line("load \"abc\"", &mut label_ids).unwrap_err();
+        assert_eq!(error, ParseErrorKind::UnsupportedSyntax);
+        let error = parse_line("print \"abc\" 1", &mut label_ids).unwrap_err();
+        assert_eq!(error, ParseErrorKind::UnsupportedSyntax);
+        let error = parse_line("print \"abc\"", &mut label_ids).unwrap_err();
+        assert_eq!(error, ParseErrorKind::UnsupportedSyntax);
+
+        let mut symbols = Symbols::default();
+        let extensions = Extensions::<i64>::default();
+        let line = parse_line_with_extensions("write \"abc\"", &mut symbols, &extensions);
+        let id = symbols.strings["abc"];
+        assert_eq!(line.unwrap().op, Some(Print(id)));
+    }
+
+    #[test]
+    fn test_parse_host_call() {
+        let program = parse("sys random\nsys log\nsys random").unwrap();
+        let ids: Vec<_> = program
+            .instructions
+            .iter()
+            .map(|stmt| match stmt.op {
+                Sys(id) => id,
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(ids[0], ids[2]);
+        assert_ne!(ids[0], ids[1]);
+        assert_eq!(program.decode_host_call(ids[0]), Some("random"));
+        assert_eq!(program.decode_host_call(ids[1]), Some("log"));
+
+        let mut label_ids = HashMap::default();
+        let error = parse_line("SYS 1", &mut label_ids).unwrap_err();
+        assert_eq!(error, ParseErrorKind::not_valid_argument());
+        let error = parse_line("SYS random", &mut label_ids).unwrap_err();
+        assert_eq!(error, ParseErrorKind::UnsupportedSyntax);
+
+        let mut symbols = Symbols::default();
+        let line =
+            parse_line_with_extensions("sys log", &mut symbols, &Extensions::<i64>::default());
+        let id = symbols.host_calls["log"];
+        assert_eq!(line.unwrap().op, Some(Sys(id)));
+    }
+
+    #[test]
+    fn test_parse_with_profile() {
+        let profile = Profile::counter_machine().forbid_mode(crate::stmt::AddressingMode::Indirect);
+        let source = "read 1\nload *1\nmult =2\nhalt";
+        let errors = parse_with_profile(source, &profile).unwrap_err();
+        let lines: Vec<_> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![2, 3]);
+        assert_eq!(
+            errors[1].kind.to_string(),
+            "MULT is not allowed by profile counter"
+        );
+
+        assert!(parse_with_profile(source, &Profile::full()).is_ok());
+    }
+
     #[test]
     fn test_parse_line_invalid_label() {
         let mut label_ids = HashMap::default();
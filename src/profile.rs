@@ -0,0 +1,269 @@
+//! The `profile` module defines instruction set profiles, which restrict the
+//! operations and addressing modes a program may use.
+//!
+//! A [`Profile`] is enforced by [`parse_with`] and [`parse_with_profile`]: every
+//! instruction that is not allowed by the profile is reported as
+//! [`ParseErrorKind::NotAllowed`] naming the profile.
+//!
+//! # Examples
+//!
+//! ```
+//! use ramemu::errors::ParseErrorKind;
+//! use ramemu::parser::parse_with_profile;
+//! use ramemu::profile::Profile;
+//!
+//! let profile = Profile::counter_machine();
+//!
+//! assert!(parse_with_profile("load 1\nadd =1\nstore 1\nhalt", &profile).is_ok());
+//!
+//! let errors = parse_with_profile("load 1\nmult =2\nhalt", &profile).unwrap_err();
+//! assert_eq!(errors[0].line, 2);
+//! assert_eq!(
+//!     errors[0].kind,
+//!     ParseErrorKind::NotAllowed {
+//!         profile: "counter".into(),
+//!         feature: "MULT".into()
+//!     }
+//! );
+//! ```
+//!
+//! [`parse_with`]: crate::parser::parse_with
+//! [`parse_with_profile`]: crate::parser::parse_with_profile
+//! [`ParseErrorKind::NotAllowed`]: crate::errors::ParseErrorKind::NotAllowed
+
+use rustc_hash::FxHashSet as HashSet;
+
+use crate::errors::ParseErrorKind;
+use crate::stmt::AddressingMode;
+use crate::stmt::Op;
+use crate::stmt::OpKind;
+
+/// A named set of allowed operations and addressing modes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    name: String,
+    ops: HashSet<OpKind>,
+    modes: HashSet<AddressingMode>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self::full()
+    }
+}
+
+impl Profile {
+    /// Creates a new empty [`Profile`] that allows nothing.
+    ///
+    /// Use [`Profile::allow`] and [`Profile::allow_mode`] to build a custom allow-list.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ops: HashSet::default(),
+            modes: HashSet::default(),
+        }
+    }
+
+    /// Creates a custom [`Profile`] from allow-lists of operations and addressing modes.
+    #[must_use]
+    pub fn custom(
+        name: impl Into<String>,
+        ops: impl IntoIterator<Item = OpKind>,
+        modes: impl IntoIterator<Item = AddressingMode>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            ops: ops.into_iter().collect(),
+            modes: modes.into_iter().collect(),
+        }
+    }
+
+    /// The `full` profile: every operation and addressing mode is allowed.
+    #[must_use]
+    pub fn full() -> Self {
+        Self::custom("full", OpKind::ALL, AddressingMode::ALL)
+    }
+
+    /// The `classic` profile: the textbook RAM instruction set, without unary
+    /// operations, host calls and custom instructions.
+    #[must_use]
+    pub fn classic() -> Self {
+        use OpKind::{
+            Add, Div, Halt, Input, Jump, JumpGreatherZero, JumpIfZero, Load, Mult, Output, Store,
+            Sub,
+        };
+        Self::custom(
+            "classic",
+            [
+                Load,
+                Store,
+                Add,
+                Sub,
+                Mult,
+                Div,
+                Jump,
+                JumpIfZero,
+                JumpGreatherZero,
+                Input,
+                Output,
+                Halt,
+            ],
+            AddressingMode::ALL,
+        )
+    }
+
+    /// The `counter` profile: a counter-machine style instruction set with
+    /// `LOAD`, `STORE`, `ADD`, `SUB`, `JZERO`, `READ`, `WRITE` and `HALT`.
+    #[must_use]
+    pub fn counter_machine() -> Self {
+        use OpKind::{Add, Halt, Input, JumpIfZero, Load, Output, Store, Sub};
+        Self::custom(
+            "counter",
+            [Load, Store, Add, Sub, JumpIfZero, Input, Output, Halt],
+            AddressingMode::ALL,
+        )
+    }
+
+    /// Returns a predefined profile by its name: `full`, `classic` or `counter`.
+    ///
+    /// If there is no such profile, returns `None`.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "full" => Some(Self::full()),
+            "classic" => Some(Self::classic()),
+            "counter" => Some(Self::counter_machine()),
+            _ => None,
+        }
+    }
+
+    /// Allows an operation.
+    #[must_use]
+    pub fn allow(mut self, op: OpKind) -> Self {
+        self.ops.insert(op);
+        self
+    }
+
+    /// Forbids an operation.
+    #[must_use]
+    pub fn forbid(mut self, op: OpKind) -> Self {
+        self.ops.remove(&op);
+        self
+    }
+
+    /// Allows an addressing mode.
+    #[must_use]
+    pub fn allow_mode(mut self, mode: AddressingMode) -> Self {
+        self.modes.insert(mode);
+        self
+    }
+
+    /// Forbids an addressing mode.
+    #[must_use]
+    pub fn forbid_mode(mut self, mode: AddressingMode) -> Self {
+        self.modes.remove(&mode);
+        self
+    }
+
+    /// Returns the name of the profile.
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns `true` if the profile allows the operation.
+    #[inline]
+    #[must_use]
+    pub fn allows(&self, op: OpKind) -> bool {
+        self.ops.contains(&op)
+    }
+
+    /// Returns `true` if the profile allows the addressing mode.
+    #[inline]
+    #[must_use]
+    pub fn allows_mode(&self, mode: AddressingMode) -> bool {
+        self.modes.contains(&mode)
+    }
+
+    /// Checks that the operation and its addressing mode are allowed.
+    /// # Errors
+    /// Returns [`ParseErrorKind::NotAllowed`] if they are not.
+    pub fn check<T>(&self, op: &Op<T>) -> Result<(), ParseErrorKind> {
+        if !self.allows(op.kind()) {
+            return Err(self.not_allowed(op.kind()));
+        }
+        let rhs = match op {
+            Op::Assert(_, _, rhs) => Some(rhs.addressing_mode()),
+            _ => None,
+        };
+        match op
+            .addressing_mode()
+            .into_iter()
+            .chain(rhs)
+            .find(|&mode| !self.allows_mode(mode))
+        {
+            Some(mode) => Err(self.not_allowed(mode)),
+            None => Ok(()),
+        }
+    }
+
+    fn not_allowed(&self, feature: impl ToString) -> ParseErrorKind {
+        ParseErrorKind::NotAllowed {
+            profile: self.name.clone(),
+            feature: feature.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stmt::{RegisterValue, Value};
+
+    #[test]
+    fn test_full_allows_everything() {
+        let profile = Profile::full();
+        assert!(OpKind::ALL.iter().all(|&op| profile.allows(op)));
+        assert!(AddressingMode::ALL.iter().all(|&m| profile.allows_mode(m)));
+    }
+
+    #[test]
+    fn test_check_op() {
+        let profile = Profile::counter_machine();
+        assert_eq!(profile.check(&Op::Load(Value::Pure(1))), Ok(()));
+        assert_eq!(
+            profile.check(&Op::Div(Value::Pure(1))),
+            Err(ParseErrorKind::NotAllowed {
+                profile: "counter".into(),
+                feature: "DIV".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_addressing_mode() {
+        let profile = Profile::classic().forbid_mode(AddressingMode::Indirect);
+        assert_eq!(
+            profile.check(&Op::<i64>::Store(RegisterValue::Direct(1))),
+            Ok(())
+        );
+        assert_eq!(
+            profile.check(&Op::<i64>::Store(RegisterValue::Indirect(1))),
+            Err(ParseErrorKind::NotAllowed {
+                profile: "classic".into(),
+                feature: "Indirect Addressing".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(
+            Profile::from_name("counter"),
+            Some(Profile::counter_machine())
+        );
+        assert_eq!(Profile::from_name("unknown"), None);
+    }
+}
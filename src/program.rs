@@ -4,12 +4,24 @@
 
 use rustc_hash::FxHashMap as HashMap;
 
-use crate::{errors::ParseError, parser::parse, stmt::Stmt};
+use crate::errors::ParseError;
+use crate::extension::Extensions;
+use crate::parser::parse_with_extensions;
+use crate::stmt::Stmt;
+use crate::word::Word;
 
 /// Represents a label id.
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct LabelId(pub usize);
 
+/// Represents a host call id.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct HostCallId(pub usize);
+
+/// Represents a string literal id.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct StringId(pub usize);
+
 /// Represents a code address.
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct CodeAddress(pub usize);
@@ -33,21 +45,47 @@ impl From<usize> for LabelId {
     }
 }
 
+impl From<usize> for HostCallId {
+    fn from(value: usize) -> Self {
+        Self(value)
+    }
+}
+
+impl From<usize> for StringId {
+    fn from(value: usize) -> Self {
+        Self(value)
+    }
+}
+
 /// Represents a program code.
 ///
 /// The [`Program`] struct contains the instructions and labels of a program,
 /// and provides methods for creating, modifying, and querying the program
 /// structure.
-#[derive(Default, Debug, Clone, PartialEq)]
-pub struct Program {
+///
+/// `T` is the type of the machine word the pure values are stored as.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program<T = i64> {
     /// Instructions of the program.
-    pub instructions: Vec<Stmt>,
+    pub instructions: Vec<Stmt<T>>,
     /// Label id -> Code Address mapping.
     /// Should not have equal elements.
     pub labels: HashMap<LabelId, CodeAddress>,
+    /// Label name -> Label id mapping.
+    pub label_names: HashMap<String, LabelId>,
+    /// Host call id -> Host call name mapping.
+    pub host_calls: HashMap<HostCallId, String>,
+    /// String id -> String literal mapping.
+    pub strings: HashMap<StringId, String>,
 }
 
-impl Program {
+impl<T> Default for Program<T> {
+    fn default() -> Self {
+        Self::from(Vec::new(), HashMap::default())
+    }
+}
+
+impl<T> Program<T> {
     /// Creates a new [`Program`] from an iterable of [`Stmt`].
     ///
     /// This method initializes the labels in the program. Labels are assigned a
@@ -62,7 +100,7 @@ impl Program {
     /// use ramemu::stmt::{Op::*, Stmt, Value};
     /// use std::io::BufReader;
     /// use std::io::BufWriter;
-
+    ///
     /// let instructions = vec![
     ///     Stmt::new(Load(Value::Pure(2)), 1),
     ///     Stmt::new(Add(Value::Pure(2)), 3),
@@ -75,36 +113,24 @@ impl Program {
     ///
     /// # Errors
     ///
-    pub fn from<T>(instructions: T, labels: HashMap<LabelId, CodeAddress>) -> Self
+    pub fn from<I>(instructions: I, labels: HashMap<LabelId, CodeAddress>) -> Self
     where
-        T: IntoIterator<Item = Stmt>,
+        I: IntoIterator<Item = Stmt<T>>,
     {
         Self {
             instructions: instructions.into_iter().collect(),
             labels,
+            label_names: HashMap::default(),
+            host_calls: HashMap::default(),
+            strings: HashMap::default(),
         }
     }
 
-    /// Creates a new [`Program`] from the source code.
-    ///
-    /// This method parses the source code, creating a [`Program`] with the resulting
-    /// instructions and labels.
-    /// # Errors
-    /// If the source code is invalid, returns a [`ParseError`].
-    #[allow(clippy::missing_panics_doc)]
-    pub fn from_source(source: &str) -> Result<Self, ParseError> {
-        parse(source).map_err(|e| {
-            e.into_iter()
-                .next()
-                .expect("Allways has at least one error.")
-        })
-    }
-
     /// Returns the instruction at the given index.
     ///
     /// If the index is out of bounds, returns `None`.
     #[inline]
-    pub fn get(&self, index: impl Into<CodeAddress>) -> Option<&Stmt> {
+    pub fn get(&self, index: impl Into<CodeAddress>) -> Option<&Stmt<T>> {
         self.instructions.get(index.into().0)
     }
 
@@ -115,15 +141,58 @@ impl Program {
     pub fn decode_label(&self, label: impl Into<LabelId>) -> Option<CodeAddress> {
         self.labels.get(&label.into()).copied()
     }
+
+    /// Finds the instruction index of the label with the given name.
+    ///
+    /// If the label is not defined, returns `None`.
+    #[inline]
+    pub fn find_label(&self, name: &str) -> Option<CodeAddress> {
+        self.label_names
+            .get(name)
+            .and_then(|&label| self.decode_label(label))
+    }
+
+    /// Decodes the host call id into the name of the host call.
+    ///
+    /// If the host call is not found, returns `None`.
+    #[inline]
+    pub fn decode_host_call(&self, id: impl Into<HostCallId>) -> Option<&str> {
+        self.host_calls.get(&id.into()).map(String::as_str)
+    }
+
+    /// Decodes the string id into the string literal.
+    ///
+    /// If the string is not found, returns `None`.
+    #[inline]
+    pub fn decode_string(&self, id: impl Into<StringId>) -> Option<&str> {
+        self.strings.get(&id.into()).map(String::as_str)
+    }
 }
 
-impl AsRef<Self> for Program {
+impl<T: Word> Program<T> {
+    /// Creates a new [`Program`] from the source code.
+    ///
+    /// This method parses the source code, creating a [`Program`] with the resulting
+    /// instructions and labels. Pure values are parsed as `T`.
+    /// # Errors
+    /// If the source code is invalid, returns a [`ParseError`].
+    #[allow(clippy::missing_panics_doc)]
+    pub fn from_source(source: &str) -> Result<Self, ParseError> {
+        parse_with_extensions(source, &Extensions::default()).map_err(|e| {
+            e.into_iter()
+                .next()
+                .expect("Allways has at least one error.")
+        })
+    }
+}
+
+impl<T> AsRef<Self> for Program<T> {
     fn as_ref(&self) -> &Self {
         self
     }
 }
 
-impl AsMut<Self> for Program {
+impl<T> AsMut<Self> for Program<T> {
     fn as_mut(&mut self) -> &mut Self {
         self
     }
@@ -159,6 +228,13 @@ mod tests {
         assert_eq!(program.get(6), None);
     }
 
+    #[test]
+    fn find_label_test() {
+        let program: Program = Program::from_source("load =1\nloop: jmp loop\nhalt").unwrap();
+        assert_eq!(program.find_label("loop"), Some(CodeAddress(1)));
+        assert_eq!(program.find_label("end"), None);
+    }
+
     #[test]
     fn decode_label_test() {
         let program = get_test_program();
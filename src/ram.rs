@@ -56,40 +56,379 @@
 //! ```
 //!
 //! This module enables the creation of a RAM machine and provides the necessary functionalities to execute, debug, and manage its state.
+use std::cell::RefCell;
 use std::fmt::Debug;
+use std::fmt::Display;
 use std::fmt::Formatter;
 use std::io::BufRead;
 use std::io::Write;
 use std::iter::FusedIterator;
+use std::ops::Range;
+use std::rc::Rc;
 
+use rustc_hash::FxHashMap as HashMap;
+use rustc_hash::FxHashSet as HashSet;
+
+use crate::errors::DeviceMapError;
 use crate::errors::InterpretError;
+use crate::errors::InterpretErrorKind;
+use crate::extension::Context;
+use crate::extension::Extensions;
+use crate::mmio::Device;
 use crate::program::CodeAddress;
+use crate::program::LabelId;
 use crate::program::Program;
+use crate::random::Rng;
+use crate::rasp;
 use crate::registers::RegisterId;
 use crate::registers::Registers;
+use crate::registers::StorageKind;
 use crate::stmt::Op::{
-    Add, Div, Halt, Input, Jump, JumpGreatherZero, JumpIfZero, Load, Mult, Output, Store, Sub,
+    Abs, Add, Assert, Clock, Custom, Dec, Div, Floor, Halt, Inc, Input, InputChar, Jump,
+    JumpGreatherZero, JumpIfZero, Load, Mult, Neg, Output, OutputChar, Print, Rand, Store, Sub,
+    Sys, Trunc,
 };
 use crate::stmt::RegisterValue;
 use crate::stmt::Stmt;
 use crate::stmt::Value;
+use crate::trace::{Access, AccessKind, MemoryTrace};
+use crate::word::DivisionMode;
+use crate::word::OverflowMode;
+use crate::word::Word;
 
 use crate::errors::InterpretErrorKind::{
-    DivisionByZero, Halted, IOError, InvalidInput, InvalidLiteral, SegmentationFault, UnknownLabel,
+    AssertionFailed, DeviceError, EndOfInput, EndOfProgram, Halted, HostCallError, IOError,
+    InvalidCharacter, InvalidInput, InvalidLiteral, InvalidRange, Overflow, SegmentationFault,
+    StepLimitExceeded, UninitializedRead, UnknownHostCall, UnknownInstruction, UnknownLabel,
 };
 
+/// The value `READC` stores when the input is exhausted.
+///
+/// Words that can not represent it, e.g. `u64`, stop the machine with
+/// [`EndOfInput`] instead.
+///
+/// [`EndOfInput`]: crate::errors::InterpretErrorKind::EndOfInput
+pub const EOF: i64 = -1;
+
+/// The number of registers [`Ram::from_program`] sets to zero.
+pub const PREFILLED_REGISTERS: usize = 100;
+
+/// A host function that can be called from a program with `SYS name`.
+///
+/// The function gets the same [`Context`] as custom instructions. An `Err`
+/// message is reported as [`HostCallError`].
+///
+/// [`HostCallError`]: crate::errors::InterpretErrorKind::HostCallError
+pub type HostCall<T = i64> = Box<dyn FnMut(&mut Context<T>) -> Result<(), String>>;
+
+/// A [`Device`] with the range of registers it is mapped to.
+type MappedDevice<T> = (Range<usize>, RefCell<Box<dyn Device<T>>>);
+
+/// Determines where the [`Ram`] fetches its instructions from.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ExecutionMode {
+    /// Instructions are fetched from the [`Program`], which is separate from the registers.
+    #[default]
+    Program,
+    /// Instructions are fetched from the registers, see the [`rasp`] module.
+    /// The program counter is a register address and jump labels are register
+    /// addresses too, so the program may modify itself.
+    Rasp,
+}
+
+/// Determines how `READ` splits the input into values.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InputMode {
+    /// Every `READ` consumes one line of input.
+    #[default]
+    Line,
+    /// Every `READ` consumes one token. Tokens are separated by whitespace,
+    /// including line breaks, and by any of the given additional separators.
+    Tokens(Vec<char>),
+}
+
+impl InputMode {
+    /// Returns [`InputMode::Tokens`] separated by whitespace only.
+    #[inline]
+    #[must_use]
+    pub fn tokens() -> Self {
+        Self::Tokens(Vec::new())
+    }
+}
+
+/// A function that formats a value written by `WRITE`.
+pub type ValueFormatter<T = i64> = Rc<dyn Fn(&T) -> String>;
+
+/// Determines how `WRITE` formats and separates values.
+///
+/// The separator is written only between two consecutive values, so string
+/// literals and characters written in between are not affected by it.
+///
+/// # Examples
+///
+/// ```
+/// use ramemu::ram::OutputPolicy;
+///
+/// let policy = OutputPolicy::separated(", ").with_formatter(|value: &i64| format!("{value:x}"));
+/// assert_eq!(policy.format(&255), "ff");
+/// assert_eq!(policy.separator(), ", ");
+/// ```
+#[derive(Clone)]
+pub struct OutputPolicy<T = i64> {
+    separator: String,
+    newline_on_halt: bool,
+    formatter: Option<ValueFormatter<T>>,
+}
+
+impl<T> Default for OutputPolicy<T> {
+    fn default() -> Self {
+        Self {
+            separator: String::new(),
+            newline_on_halt: false,
+            formatter: None,
+        }
+    }
+}
+
+impl<T: Display> OutputPolicy<T> {
+    /// Writes every value on its own line, including a trailing newline on halt.
+    #[must_use]
+    pub fn newline() -> Self {
+        Self::separated("\n").with_newline_on_halt(true)
+    }
+
+    /// Separates values with a single space.
+    #[must_use]
+    pub fn space() -> Self {
+        Self::separated(" ")
+    }
+
+    /// Separates values with a custom separator.
+    #[must_use]
+    pub fn separated(separator: impl Into<String>) -> Self {
+        Self {
+            separator: separator.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets whether a newline is written on `HALT` if the output does not end with one.
+    #[must_use]
+    pub fn with_newline_on_halt(mut self, newline_on_halt: bool) -> Self {
+        self.newline_on_halt = newline_on_halt;
+        self
+    }
+
+    /// Sets a custom formatter for values.
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: impl Fn(&T) -> String + 'static) -> Self {
+        self.formatter = Some(Rc::new(formatter));
+        self
+    }
+
+    /// Returns the separator written between two consecutive values.
+    #[inline]
+    #[must_use]
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
+
+    /// Returns `true` if a newline is written on `HALT`.
+    #[inline]
+    #[must_use]
+    pub fn newline_on_halt(&self) -> bool {
+        self.newline_on_halt
+    }
+
+    /// Formats a value, by default in decimal.
+    #[must_use]
+    pub fn format(&self, value: &T) -> String {
+        match &self.formatter {
+            Some(formatter) => formatter(value),
+            None => value.to_string(),
+        }
+    }
+}
+
+impl<T> Debug for OutputPolicy<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OutputPolicy")
+            .field("separator", &self.separator)
+            .field("newline_on_halt", &self.newline_on_halt)
+            .field("formatter", &self.formatter.is_some())
+            .finish()
+    }
+}
+
+/// Determines how `READC` and `WRITEC` convert between characters and values.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CharEncoding {
+    /// Characters are single bytes, values are in `0..=255`.
+    Byte,
+    /// Characters are Unicode scalar values encoded as UTF-8.
+    #[default]
+    Unicode,
+}
+
+/// The input and output tapes of the textbook RAM.
+///
+/// In tape mode `READ` and `READC` take the value under the head of the input
+/// tape and move the head, while `WRITE` and `WRITEC` append values to the
+/// output tape. String literals are still written to the writer.
+///
+/// # Examples
+///
+/// ```
+/// use ramemu::program::Program;
+/// use ramemu::ram::{Ram, Tapes};
+/// use std::io::BufReader;
+/// use std::io::BufWriter;
+///
+/// let program = Program::from_source("read 1\nread 2\nload 1\nadd 2\nwrite 0\nhalt").unwrap();
+/// let reader = BufReader::new(std::io::empty());
+/// let writer = BufWriter::new(std::io::sink());
+/// let mut ram =
+///     Ram::new(program, Box::new(reader), Box::new(writer)).with_tapes(Tapes::new([2, 3, 4]));
+///
+/// ram.run().unwrap();
+/// let tapes = ram.get_tapes().unwrap();
+/// assert_eq!(tapes.head, 2);
+/// assert_eq!(tapes.output, [5]);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Tapes<T = i64> {
+    /// The read-only input tape.
+    pub input: Vec<T>,
+    /// Position of the next value to be read from the input tape.
+    pub head: usize,
+    /// The write-only output tape.
+    pub output: Vec<T>,
+}
+
+impl<T: Clone> Tapes<T> {
+    /// Creates new [`Tapes`] with the given input tape and an empty output tape.
+    #[must_use]
+    pub fn new(input: impl Into<Vec<T>>) -> Self {
+        Self {
+            input: input.into(),
+            head: 0,
+            output: Vec::new(),
+        }
+    }
+
+    /// Reads the value under the head and moves the head forward.
+    ///
+    /// If the input tape is exhausted, returns `None`.
+    pub fn read(&mut self) -> Option<T> {
+        let value = self.input.get(self.head)?.clone();
+        self.head += 1;
+        Some(value)
+    }
+
+    /// Appends a value to the output tape.
+    #[inline]
+    pub fn write(&mut self, value: T) {
+        self.output.push(value);
+    }
+}
+
+/// Bounds the registers a program may use.
+///
+/// Accessing a register past the limit stops the machine with a
+/// [`SegmentationFault`] that holds the offending address. Register `0`, the
+/// accumulator, is always available and does not count as a touched register.
+/// Writes through [`Context::set`] are bounded the same way.
+///
+/// # Examples
+///
+/// ```
+/// use ramemu::ram::MemoryLimit;
+///
+/// let limit = MemoryLimit::unlimited().with_max_index(1023).with_max_registers(64);
+/// assert_eq!(limit.max_index(), Some(1023));
+/// assert_eq!(limit.max_registers(), Some(64));
+/// ```
+///
+/// [`SegmentationFault`]: crate::errors::InterpretErrorKind::SegmentationFault
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MemoryLimit {
+    max_index: Option<usize>,
+    max_registers: Option<usize>,
+}
+
+impl MemoryLimit {
+    /// Allows every register, the default.
+    #[must_use]
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Sets the highest register index a program may read or write.
+    #[must_use]
+    pub fn with_max_index(mut self, max_index: usize) -> Self {
+        self.max_index = Some(max_index);
+        self
+    }
+
+    /// Sets how many distinct registers a program may write.
+    #[must_use]
+    pub fn with_max_registers(mut self, max_registers: usize) -> Self {
+        self.max_registers = Some(max_registers);
+        self
+    }
+
+    /// Returns the highest register index a program may access, if limited.
+    #[inline]
+    #[must_use]
+    pub fn max_index(&self) -> Option<usize> {
+        self.max_index
+    }
+
+    /// Returns how many distinct registers a program may write, if limited.
+    #[inline]
+    #[must_use]
+    pub fn max_registers(&self) -> Option<usize> {
+        self.max_registers
+    }
+}
+
 /// The [`Ram`] struct represents a Random Access Machine (RAM).
 ///
 /// It holds the program, registers, program counter, line number, halt state, error state, input reader, and output writer.
-pub struct Ram {
-    program: Program,
-    registers: Registers<i64>,
-    pc: CodeAddress,
-    line: usize,
-    halt: bool,
-    error: Option<InterpretError>,
-    reader: Box<dyn BufRead>,
-    writer: Box<dyn Write>,
+///
+/// The registers hold values of a [`Word`] type, `i64` by default. Use
+/// [`Ram::new`] for the integer RAM and [`Ram::from_program`] for other words.
+pub struct Ram<T = i64> {
+    pub(crate) program: Program<T>,
+    pub(crate) registers: Registers<T>,
+    pub(crate) pc: CodeAddress,
+    pub(crate) line: usize,
+    pub(crate) halt: bool,
+    pub(crate) error: Option<InterpretError>,
+    pub(crate) reader: Box<dyn BufRead>,
+    pub(crate) writer: Box<dyn Write>,
+    pub(crate) extensions: Extensions<T>,
+    pub(crate) host_calls: HashMap<String, HostCall<T>>,
+    pub(crate) mode: ExecutionMode,
+    pub(crate) char_encoding: CharEncoding,
+    pub(crate) input_mode: InputMode,
+    pub(crate) output_policy: OutputPolicy<T>,
+    pub(crate) pending_separator: bool,
+    pub(crate) line_open: bool,
+    pub(crate) rng: Rng,
+    pub(crate) clock: u64,
+    pub(crate) assertions: bool,
+    pub(crate) overflow: OverflowMode,
+    pub(crate) division: DivisionMode,
+    pub(crate) memory_limit: MemoryLimit,
+    pub(crate) touched: HashSet<RegisterId>,
+    pub(crate) strict: bool,
+    pub(crate) prefilled: bool,
+    pub(crate) step_limit: Option<u64>,
+    pub(crate) initialized: HashSet<RegisterId>,
+    pub(crate) trace: Option<MemoryTrace>,
+    pub(crate) devices: Vec<MappedDevice<T>>,
+    pub(crate) tapes: Option<Tapes<T>>,
 }
 
 impl Ram {
@@ -97,29 +436,332 @@ impl Ram {
     #[inline]
     #[must_use]
     pub fn new(program: Program, reader: Box<dyn BufRead>, writer: Box<dyn Write>) -> Self {
+        Self::from_program(program, reader, writer)
+    }
+}
+
+impl<T: Word> Ram<T> {
+    /// Creates a new [`Ram`] instance for any [`Word`] type with the given
+    /// program, input reader, and output writer.
+    ///
+    /// The first [`PREFILLED_REGISTERS`] registers start out as zero. In
+    /// [strict mode](Ram::with_strict) they still count as never written.
+    #[inline]
+    #[must_use]
+    pub fn from_program(
+        program: Program<T>,
+        reader: Box<dyn BufRead>,
+        writer: Box<dyn Write>,
+    ) -> Self {
         Self {
             program,
-            registers: [0; 100].into(),
+            registers: std::iter::repeat_n(T::default(), PREFILLED_REGISTERS).collect(),
             pc: CodeAddress::default(),
             line: 0,
             halt: false,
             error: None,
             reader,
             writer,
+            extensions: Extensions::default(),
+            host_calls: HashMap::default(),
+            mode: ExecutionMode::Program,
+            char_encoding: CharEncoding::Unicode,
+            input_mode: InputMode::Line,
+            output_policy: OutputPolicy::default(),
+            pending_separator: false,
+            line_open: false,
+            rng: Rng::default(),
+            clock: 0,
+            assertions: true,
+            overflow: OverflowMode::Checked,
+            division: DivisionMode::Truncating,
+            memory_limit: MemoryLimit::unlimited(),
+            touched: HashSet::default(),
+            strict: false,
+            prefilled: true,
+            step_limit: None,
+            initialized: HashSet::default(),
+            trace: None,
+            devices: Vec::new(),
+            tapes: None,
+        }
+    }
+
+    /// Creates a new [`Ram`] instance in [`ExecutionMode::Rasp`], which runs the
+    /// program stored in the memory image starting at register address `entry`.
+    ///
+    /// Use [`rasp::encode`] to build the image from a [`Program`].
+    #[inline]
+    #[must_use]
+    pub fn from_image(
+        image: Registers<T>,
+        entry: CodeAddress,
+        reader: Box<dyn BufRead>,
+        writer: Box<dyn Write>,
+    ) -> Self {
+        Self {
+            registers: image,
+            prefilled: false,
+            pc: entry,
+            mode: ExecutionMode::Rasp,
+            ..Self::from_program(Program::default(), reader, writer)
+        }
+    }
+
+    /// Sets the custom instructions the [`Ram`] instance can execute.
+    ///
+    /// Must be the same [`Extensions`] that were used to parse the program.
+    #[inline]
+    #[must_use]
+    pub fn with_extensions(mut self, extensions: Extensions<T>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// Sets how `READ` splits the input into values.
+    #[inline]
+    #[must_use]
+    pub fn with_input_mode(mut self, input_mode: InputMode) -> Self {
+        self.input_mode = input_mode;
+        self
+    }
+
+    /// Switches the [`Ram`] to tape I/O: values are read from and written to
+    /// the given [`Tapes`] instead of the reader and the writer.
+    #[inline]
+    #[must_use]
+    pub fn with_tapes(mut self, tapes: Tapes<T>) -> Self {
+        self.tapes = Some(tapes);
+        self
+    }
+
+    /// Seeds the pseudo-random number generator used by `RAND`.
+    ///
+    /// The default seed is `0`.
+    #[inline]
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Rng::new(seed);
+        self
+    }
+
+    /// Enables or disables `ASSERT` instructions. Disabled assertions are not
+    /// evaluated at all.
+    ///
+    /// Assertions are enabled by default.
+    #[inline]
+    #[must_use]
+    pub fn with_assertions(mut self, enabled: bool) -> Self {
+        self.assertions = enabled;
+        self
+    }
+
+    /// Sets how arithmetic handles results that do not fit into the word.
+    ///
+    /// The mode applies to `ADD`, `SUB`, `MULT`, `DIV`, `INC`, `DEC`, `NEG`
+    /// and `ABS`, and behaves the same in debug and release builds. The
+    /// default is [`OverflowMode::Checked`].
+    #[inline]
+    #[must_use]
+    pub fn with_overflow_mode(mut self, overflow: OverflowMode) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Sets how `DIV` rounds a quotient that is not exact. The default is
+    /// [`DivisionMode::Truncating`].
+    ///
+    /// Division by zero is always a [`DivisionByZero`] error, independent of
+    /// the [`OverflowMode`].
+    ///
+    /// [`DivisionByZero`]: crate::errors::InterpretErrorKind::DivisionByZero
+    #[inline]
+    #[must_use]
+    pub fn with_division_mode(mut self, division: DivisionMode) -> Self {
+        self.division = division;
+        self
+    }
+
+    /// Limits the registers the program may use, see [`MemoryLimit`].
+    ///
+    /// Registers of a RASP memory image count only once the program writes
+    /// them, but the image must fit below the maximum index to be fetched.
+    ///
+    /// Removing the maximum index moves [dense](StorageKind::Dense) registers
+    /// into [hybrid](StorageKind::hybrid) storage, see [`Ram::with_storage`].
+    #[inline]
+    #[must_use]
+    pub fn with_memory_limit(mut self, memory_limit: MemoryLimit) -> Self {
+        self.memory_limit = memory_limit;
+        let kind = self.registers.storage_kind();
+        self.with_storage(kind)
+    }
+
+    /// Moves the registers into the given kind of storage, see [`StorageKind`].
+    ///
+    /// Dense and hybrid storage avoid hashing register indices, which pays
+    /// off most in loops over many registers, see `cargo bench --bench storage`. Dense storage allocates every register
+    /// up to the highest one written, so it is only used if the
+    /// [`MemoryLimit`] has a maximum index. Otherwise, e.g. if the memory
+    /// limit is set afterwards, the registers are kept in
+    /// [hybrid](StorageKind::hybrid) storage.
+    #[inline]
+    #[must_use]
+    pub fn with_storage(mut self, kind: StorageKind) -> Self {
+        let kind = match kind {
+            StorageKind::Dense if self.memory_limit.max_index.is_none() => StorageKind::hybrid(),
+            kind => kind,
+        };
+        if kind != self.registers.storage_kind() {
+            self.registers = self.registers.with_storage(kind);
+        }
+        self
+    }
+
+    /// Limits the number of instructions the [`Ram`] executes. Executing one
+    /// more is a [`StepLimitExceeded`] error.
+    ///
+    /// [`StepLimitExceeded`]: crate::errors::InterpretErrorKind::StepLimitExceeded
+    #[inline]
+    #[must_use]
+    pub fn with_step_limit(mut self, step_limit: u64) -> Self {
+        self.step_limit = Some(step_limit);
+        self
+    }
+
+    /// Records every register access into the given trace, see [`MemoryTrace`].
+    #[inline]
+    #[must_use]
+    pub fn with_memory_trace(mut self, trace: MemoryTrace) -> Self {
+        self.trace = Some(trace);
+        self
+    }
+
+    /// Enables or disables strict mode, in which reading a register that was
+    /// never written, including the accumulator, is an [`UninitializedRead`]
+    /// error.
+    ///
+    /// Registers that have been set when strict mode is enabled, such as the
+    /// words of a RASP memory image, count as initialized, even if they were
+    /// set to zero. The zeros [`Ram::from_program`] fills the first
+    /// [`PREFILLED_REGISTERS`] registers with do not. Strict mode is disabled
+    /// by default.
+    ///
+    /// [`UninitializedRead`]: crate::errors::InterpretErrorKind::UninitializedRead
+    #[must_use]
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self.initialized = if strict {
+            let prefilled = |index: RegisterId, value: &T| {
+                self.prefilled && index.0 < PREFILLED_REGISTERS && value.is_zero()
+            };
+            self.registers
+                .entries()
+                .filter(|&(index, value)| !prefilled(index, value))
+                .map(|(index, _)| index)
+                .collect()
+        } else {
+            HashSet::default()
+        };
+        self
+    }
+
+    /// Sets how `WRITE` formats and separates values.
+    #[inline]
+    #[must_use]
+    pub fn with_output_policy(mut self, output_policy: OutputPolicy<T>) -> Self {
+        self.output_policy = output_policy;
+        self
+    }
+
+    /// Sets the character encoding used by `READC` and `WRITEC`.
+    #[inline]
+    #[must_use]
+    pub fn with_char_encoding(mut self, char_encoding: CharEncoding) -> Self {
+        self.char_encoding = char_encoding;
+        self
+    }
+
+    /// Registers a host function that is executed by `SYS name`.
+    ///
+    /// Registering a function under an existing name replaces it.
+    #[inline]
+    pub fn register_host_call(
+        &mut self,
+        name: impl Into<String>,
+        function: impl FnMut(&mut Context<T>) -> Result<(), String> + 'static,
+    ) {
+        self.host_calls.insert(name.into(), Box::new(function));
+    }
+
+    /// Registers a host function like [`Ram::register_host_call`] and returns
+    /// the [`Ram`] instance.
+    #[inline]
+    #[must_use]
+    pub fn with_host_call(
+        mut self,
+        name: impl Into<String>,
+        function: impl FnMut(&mut Context<T>) -> Result<(), String> + 'static,
+    ) -> Self {
+        self.register_host_call(name, function);
+        self
+    }
+
+    /// Maps the registers in `range` to a [`Device`], see [`mmio`].
+    /// # Errors
+    /// Returns a [`DeviceMapError`] if the range is empty, contains the
+    /// accumulator `R0` or overlaps the range of another device.
+    ///
+    /// [`mmio`]: crate::mmio
+    pub fn register_device(
+        &mut self,
+        range: Range<usize>,
+        device: impl Device<T> + 'static,
+    ) -> Result<(), DeviceMapError> {
+        if range.start >= range.end {
+            return Err(DeviceMapError::EmptyRange);
         }
+        if range.start == 0 {
+            return Err(DeviceMapError::AccumulatorMapped);
+        }
+        if let Some((mapped, _)) = self
+            .devices
+            .iter()
+            .find(|(mapped, _)| mapped.start < range.end && range.start < mapped.end)
+        {
+            return Err(DeviceMapError::Overlap(mapped.start.max(range.start)));
+        }
+        self.devices.push((range, RefCell::new(Box::new(device))));
+        Ok(())
+    }
+
+    /// Maps a device like [`Ram::register_device`] and returns the [`Ram`] instance.
+    /// # Errors
+    /// Returns a [`DeviceMapError`] if the range is invalid, see [`Ram::register_device`].
+    #[inline]
+    pub fn with_device(
+        mut self,
+        range: Range<usize>,
+        device: impl Device<T> + 'static,
+    ) -> Result<Self, DeviceMapError> {
+        self.register_device(range, device)?;
+        Ok(self)
     }
 
     /// Returns a reference to the registers of the [`Ram`] instance.
     #[inline]
     #[must_use]
-    pub fn get_registers(&self) -> &Registers<i64> {
+    pub fn get_registers(&self) -> &Registers<T> {
         &self.registers
     }
 
     /// Returns the current instruction of the program as an [`Option<Stmt>`].
+    ///
+    /// In [`ExecutionMode::Rasp`] the program is empty, so this returns `None`;
+    /// use [`rasp::fetch`] on the registers instead.
     #[inline]
     #[must_use]
-    pub fn get_current_instruction(&self) -> Option<&Stmt> {
+    pub fn get_current_instruction(&self) -> Option<&Stmt<T>> {
         self.program.get(self.pc)
     }
 
@@ -142,7 +784,10 @@ impl Ram {
         let result = self.eval_current();
 
         match &result {
-            &Ok(next_pc) => self.pc = next_pc,
+            &Ok(next_pc) => {
+                self.pc = next_pc;
+                self.clock += 1;
+            }
             Err(error) => {
                 self.error = Some(error.clone());
                 self.halt = true;
@@ -152,6 +797,20 @@ impl Ram {
         result.map(|_| ())
     }
 
+    /// Returns the input and output tapes, if the [`Ram`] is in tape I/O mode.
+    #[inline]
+    #[must_use]
+    pub fn get_tapes(&self) -> Option<&Tapes<T>> {
+        self.tapes.as_ref()
+    }
+
+    /// Returns the number of instructions executed so far.
+    #[inline]
+    #[must_use]
+    pub fn get_clock(&self) -> u64 {
+        self.clock
+    }
+
     /// Returns the current error state of the [`Ram`] instance as an
     /// [`Option<InterpretError>`].
     #[inline]
@@ -167,73 +826,152 @@ impl Ram {
     /// # Errors
     /// Returns an [`InterpretError`] if the program encounters an error.
     #[inline]
-    pub fn eval(&mut self, stmt: Stmt) -> Result<CodeAddress, InterpretError> {
+    pub fn eval(&mut self, stmt: Stmt<T>) -> Result<CodeAddress, InterpretError> {
         self.line = stmt.line;
-        let mut next_pc = self.pc + 1;
+        let mut next_pc = match self.mode {
+            ExecutionMode::Program => self.pc + 1,
+            ExecutionMode::Rasp => self.pc + rasp::INSTRUCTION_SIZE,
+        };
 
         match stmt.op {
             Load(value) => self.set_first(self.get_with_value(value)?),
             Store(value) => {
-                let index: usize = self
-                    .get_with_register(value)?
-                    .try_into()
-                    .map_err(|_| InterpretError::new(SegmentationFault, self.line))?;
-                self.registers.set(RegisterId(index), self.first());
-            }
-            Add(value) => self.set_first(self.first() + self.get_with_value(value)?),
-            Sub(value) => self.set_first(self.first() - self.get_with_value(value)?),
-            Mult(value) => self.set_first(self.first() * self.get_with_value(value)?),
+                let index = self.register_index(value)?;
+                self.store(index, self.first()?)?;
+            }
+            Add(value) => {
+                let value = self.get_with_value(value)?;
+                self.set_first(self.arithmetic(self.first()?.add_with(&value, self.overflow))?);
+            }
+            Sub(value) => {
+                let value = self.get_with_value(value)?;
+                self.set_first(self.arithmetic(self.first()?.sub_with(&value, self.overflow))?);
+            }
+            Mult(value) => {
+                let value = self.get_with_value(value)?;
+                self.set_first(self.arithmetic(self.first()?.mul_with(&value, self.overflow))?);
+            }
             Div(value) => {
-                self.set_first(
-                    self.first()
-                        .checked_div(self.get_with_value(value)?)
-                        .ok_or(InterpretError::new(DivisionByZero, self.line))?,
-                );
+                let value = self.get_with_value(value)?;
+                self.set_first(self.arithmetic(self.first()?.div_with(
+                    &value,
+                    self.division,
+                    self.overflow,
+                ))?);
+            }
+            Inc(value) => {
+                let index = self.register_index(value)?;
+                let result = self.read(index)?.add_with(&self.word(1)?, self.overflow);
+                self.store(index, self.arithmetic(result)?)?;
             }
+            Dec(value) => {
+                let index = self.register_index(value)?;
+                let result = self.read(index)?.sub_with(&self.word(1)?, self.overflow);
+                self.store(index, self.arithmetic(result)?)?;
+            }
+            Neg => self.set_first(self.arithmetic(self.first()?.neg_with(self.overflow))?),
+            Abs => self.set_first(self.arithmetic(self.first()?.abs_with(self.overflow))?),
+            Floor => self.set_first(self.first()?.floor()),
+            Trunc => self.set_first(self.first()?.trunc()),
             Jump(label) => {
-                next_pc = self
-                    .program
-                    .decode_label(label)
-                    .ok_or(InterpretError::new(UnknownLabel, self.line))?;
+                next_pc = self.jump_target(label)?;
             }
             JumpIfZero(label) => {
-                if self.first() == 0 {
-                    next_pc = self
-                        .program
-                        .decode_label(label)
-                        .ok_or(InterpretError::new(UnknownLabel, self.line))?;
+                if self.first()?.is_zero() {
+                    next_pc = self.jump_target(label)?;
                 }
             }
             JumpGreatherZero(label) => {
-                if self.first() > 0 {
-                    next_pc = self
-                        .program
-                        .decode_label(label)
-                        .ok_or(InterpretError::new(UnknownLabel, self.line))?;
+                if self.first()?.is_positive() {
+                    next_pc = self.jump_target(label)?;
                 }
             }
             Output(value) => {
                 let value = self.get_with_value(value)?;
-                write!(&mut self.writer, "{value}")
-                    .map_err(|_| InterpretError::new(IOError, self.line))?;
+                self.write_value(value)?;
             }
             Input(value) => {
-                let mut input = String::new();
-                self.reader
-                    .read_line(&mut input)
-                    .map_err(|_| InterpretError::new(IOError, self.line))?;
-                let index: usize = self
-                    .get_with_register(value)?
-                    .try_into()
-                    .map_err(|_| InterpretError::new(SegmentationFault, self.line))?;
-                self.registers.set(
-                    RegisterId(index),
-                    input.trim().parse().map_err(|_| {
-                        InterpretError::new(InvalidInput(input.trim().into()), self.line)
-                    })?,
-                );
-            }
-            Halt => self.halt = true,
+                let input = self.read_number()?;
+                let index = self.register_index(value)?;
+                self.store(index, input)?;
+            }
+            OutputChar(value) => {
+                let value = self.get_with_value(value)?;
+                self.write_char(value)?;
+            }
+            Print(id) => {
+                let text = self
+                    .program
+                    .decode_string(id)
+                    .ok_or(InterpretError::new(InvalidLiteral, self.line))?
+                    .to_string();
+                self.write_bytes(text.as_bytes())?;
+            }
+            InputChar(value) => {
+                let code = self.read_char()?;
+                let index = self.register_index(value)?;
+                self.store(index, code)?;
+            }
+            Clock => {
+                let clock = T::from_u64_with(self.clock, self.overflow);
+                self.set_first(self.arithmetic(clock)?);
+            }
+            Assert(lhs, comparison, rhs) if self.assertions => {
+                let lhs = self.get_with_value(lhs)?;
+                let rhs = self.get_with_value(rhs)?;
+                if !comparison.holds(&lhs, &rhs) {
+                    return Err(InterpretError::new(
+                        AssertionFailed {
+                            lhs: lhs.to_string().into(),
+                            comparison,
+                            rhs: rhs.to_string().into(),
+                        },
+                        self.line,
+                    ));
+                }
+            }
+            Assert(..) => {}
+            Rand(value) => {
+                let bound = self.get_with_value(value)?;
+                let range = bound.to_i64().filter(|&bound| bound > 0).ok_or_else(|| {
+                    InterpretError::new(InvalidRange(bound.to_string().into()), self.line)
+                })?;
+                let value = self.rng.below(range.unsigned_abs()) as i64;
+                let value = self.word(value)?;
+                self.set_first(value);
+            }
+            Sys(id) => {
+                let name = self.program.decode_host_call(id).unwrap_or_default();
+                let Some((name, mut function)) = self.host_calls.remove_entry(name) else {
+                    return Err(InterpretError::new(UnknownHostCall(name.into()), self.line));
+                };
+                let line = self.line;
+                let mut ctx = Context::new(self, next_pc);
+                let result = function(&mut ctx);
+                next_pc = ctx.next_pc();
+                self.host_calls.insert(name, function);
+                result
+                    .map_err(|message| InterpretError::new(HostCallError(message.into()), line))?;
+            }
+            Halt => {
+                if self.output_policy.newline_on_halt() && self.line_open {
+                    self.write_bytes(b"\n")?;
+                }
+                self.halt = true;
+            }
+            Custom(id, operand) => {
+                let instruction = self
+                    .extensions
+                    .get(id)
+                    .cloned()
+                    .ok_or(InterpretError::new(UnknownInstruction, self.line))?;
+                let line = self.line;
+                let mut ctx = Context::new(self, next_pc);
+                instruction
+                    .execute(&mut ctx, operand)
+                    .map_err(|kind| InterpretError::new(kind, line))?;
+                next_pc = ctx.next_pc();
+            }
         };
 
         Ok(next_pc)
@@ -243,27 +981,197 @@ impl Ram {
         if self.halt {
             return Err(InterpretError::new(Halted, self.line));
         }
+        if self.step_limit.is_some_and(|limit| self.clock >= limit) {
+            return Err(InterpretError::new(StepLimitExceeded, self.line));
+        }
 
-        let Some(&stmt) = self.program.get(self.pc) else {
-            return Err(InterpretError::new(SegmentationFault, self.line));
+        let stmt = match self.mode {
+            ExecutionMode::Program => self
+                .program
+                .get(self.pc)
+                .cloned()
+                .ok_or(InterpretError::new(EndOfProgram, self.line))?,
+            ExecutionMode::Rasp => {
+                self.address(self.pc.0 + rasp::INSTRUCTION_SIZE - 1)?;
+                for offset in 0..rasp::INSTRUCTION_SIZE {
+                    let access =
+                        Access::new(AccessKind::Fetch, RegisterId(self.pc.0 + offset), self.pc.0);
+                    self.record(access);
+                }
+                rasp::fetch(&self.registers, self.pc)?
+            }
         };
 
         self.eval(stmt)
     }
 
+    fn read_byte(&mut self) -> Result<Option<u8>, InterpretError> {
+        let buffer = self
+            .reader
+            .fill_buf()
+            .map_err(|_| InterpretError::new(IOError, self.line))?;
+        let Some(&byte) = buffer.first() else {
+            return Ok(None);
+        };
+        self.reader.consume(1);
+        Ok(Some(byte))
+    }
+
+    fn read_utf8(&mut self) -> Result<Option<char>, InterpretError> {
+        let Some(first) = self.read_byte()? else {
+            return Ok(None);
+        };
+
+        let width = match first {
+            0x00..=0x7F => 1,
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => 0,
+        };
+        let mut bytes = [first, 0, 0, 0];
+        for byte in bytes.iter_mut().take(width).skip(1) {
+            *byte = self.read_byte()?.unwrap_or_default();
+        }
+
+        std::str::from_utf8(&bytes[..width])
+            .ok()
+            .and_then(|s| s.chars().next())
+            .map(Some)
+            .ok_or_else(|| {
+                let input = String::from_utf8_lossy(&bytes[..width.max(1)]);
+                InterpretError::new(InvalidInput(input.into()), self.line)
+            })
+    }
+
+    fn read_char(&mut self) -> Result<T, InterpretError> {
+        let line = self.line;
+        let eof = || T::from_i64(EOF).ok_or(InterpretError::new(EndOfInput, line));
+        if let Some(value) = self.tapes.as_mut().map(Tapes::read) {
+            return value.map_or_else(eof, Ok);
+        }
+        let code = match self.char_encoding {
+            CharEncoding::Byte => self.read_byte()?.map(i64::from),
+            CharEncoding::Unicode => self.read_utf8()?.map(|c| u32::from(c).into()),
+        };
+        code.map_or_else(eof, |code| self.word(code))
+    }
+
+    /// Reads the next value for `READ` from the input tape or the reader.
+    pub(crate) fn read_number(&mut self) -> Result<T, InterpretError> {
+        if let Some(tapes) = &mut self.tapes {
+            return tapes
+                .read()
+                .ok_or(InterpretError::new(EndOfInput, self.line));
+        }
+        let input = self.read_value()?;
+        T::parse(&input).ok_or_else(|| InterpretError::new(InvalidInput(input.into()), self.line))
+    }
+
+    /// Reads the text of the next value for `READ` according to the input mode.
+    fn read_value(&mut self) -> Result<String, InterpretError> {
+        let mut input = String::new();
+
+        if self.input_mode == InputMode::Line {
+            let read = self
+                .reader
+                .read_line(&mut input)
+                .map_err(|_| InterpretError::new(IOError, self.line))?;
+            if read == 0 {
+                return Err(InterpretError::new(EndOfInput, self.line));
+            }
+            return Ok(input.trim().to_string());
+        }
+
+        loop {
+            match self.read_utf8()? {
+                Some(c) if self.is_separator(c) && input.is_empty() => continue,
+                Some(c) if self.is_separator(c) => break,
+                Some(c) => input.push(c),
+                None if input.is_empty() => return Err(InterpretError::new(EndOfInput, self.line)),
+                None => break,
+            }
+        }
+        Ok(input)
+    }
+
     #[inline]
-    fn get_with_value(&self, value: Value) -> Result<i64, InterpretError> {
+    fn is_separator(&self, c: char) -> bool {
+        c.is_whitespace()
+            || matches!(&self.input_mode, InputMode::Tokens(separators) if separators.contains(&c))
+    }
+
+    fn write_char(&mut self, value: T) -> Result<(), InterpretError> {
+        if let Some(tapes) = &mut self.tapes {
+            tapes.write(value);
+            return Ok(());
+        }
+        let invalid = || InterpretError::new(InvalidCharacter(value.to_string().into()), self.line);
+        let code = value.to_i64().ok_or_else(invalid)?;
+        let mut buffer = [0; 4];
+        let bytes: &[u8] = match self.char_encoding {
+            CharEncoding::Byte => {
+                buffer[0] = u8::try_from(code).map_err(|_| invalid())?;
+                &buffer[..1]
+            }
+            CharEncoding::Unicode => u32::try_from(code)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(invalid)?
+                .encode_utf8(&mut buffer)
+                .as_bytes(),
+        };
+        self.write_bytes(bytes)
+    }
+
+    pub(crate) fn write_value(&mut self, value: T) -> Result<(), InterpretError> {
+        if let Some(tapes) = &mut self.tapes {
+            tapes.write(value);
+            return Ok(());
+        }
+        let mut text = String::new();
+        if self.pending_separator {
+            text.push_str(self.output_policy.separator());
+        }
+        text.push_str(&self.output_policy.format(&value));
+        self.write_bytes(text.as_bytes())?;
+        self.pending_separator = true;
+        Ok(())
+    }
+
+    pub(crate) fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), InterpretError> {
+        self.writer
+            .write_all(bytes)
+            .map_err(|_| InterpretError::new(IOError, self.line))?;
+        if let Some(&last) = bytes.last() {
+            self.line_open = last != b'\n';
+        }
+        self.pending_separator = false;
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn jump_target(&self, label: LabelId) -> Result<CodeAddress, InterpretError> {
+        match self.mode {
+            ExecutionMode::Program => self
+                .program
+                .decode_label(label)
+                .ok_or(InterpretError::new(UnknownLabel, self.line)),
+            ExecutionMode::Rasp => Ok(CodeAddress(label.0)),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn get_with_value(&self, value: Value<T>) -> Result<T, InterpretError> {
         match value {
-            Value::Pure(index) => (index)
-                .try_into()
-                .map_err(|_| InterpretError::new(InvalidLiteral, self.line)),
+            Value::Pure(value) => Ok(value),
             Value::Register(RegisterValue::Direct(index)) => self.get::<1>(index),
             Value::Register(RegisterValue::Indirect(index)) => self.get::<2>(index),
         }
     }
 
     #[inline]
-    fn get_with_register(&self, value: RegisterValue) -> Result<i64, InterpretError> {
+    fn get_with_register(&self, value: RegisterValue) -> Result<T, InterpretError> {
         match value {
             RegisterValue::Direct(index) => self.get::<0>(index),
             RegisterValue::Indirect(index) => self.get::<1>(index),
@@ -271,47 +1179,161 @@ impl Ram {
     }
 
     #[inline]
-    fn set_first(&mut self, value: i64) {
-        self.registers.set(RegisterId(0), value);
+    pub(crate) fn register_index(
+        &self,
+        value: RegisterValue,
+    ) -> Result<RegisterId, InterpretError> {
+        let index = self.get_with_register(value)?;
+        match index.to_index() {
+            Some(index) => self.address(index),
+            None => Err(self.segmentation_fault(index)),
+        }
+    }
+
+    /// Checks that the register index is within the [`MemoryLimit`].
+    #[inline]
+    pub(crate) fn address(&self, index: usize) -> Result<RegisterId, InterpretError> {
+        match self.memory_limit.max_index {
+            Some(max_index) if index > max_index => Err(self.segmentation_fault(index)),
+            _ => Ok(RegisterId(index)),
+        }
+    }
+
+    /// Writes a register, counting it against the [`MemoryLimit`].
+    #[inline]
+    pub(crate) fn store(&mut self, index: RegisterId, value: T) -> Result<(), InterpretError> {
+        if let Some(device) = self.device(index) {
+            self.record(Access::new(AccessKind::Write, index, self.line));
+            return device
+                .borrow_mut()
+                .write(index.0, value)
+                .map_err(|message| InterpretError::new(DeviceError(message.into()), self.line));
+        }
+        if let Some(max_registers) = self.memory_limit.max_registers {
+            if index.0 != 0 && !self.touched.contains(&index) {
+                if self.touched.len() >= max_registers {
+                    return Err(self.segmentation_fault(index.0));
+                }
+                self.touched.insert(index);
+            }
+        }
+        self.set_register(index, value);
+        Ok(())
+    }
+
+    /// Writes a register without checking the [`MemoryLimit`], recording the access.
+    #[inline]
+    fn set_register(&mut self, index: RegisterId, value: T) {
+        self.record(Access::new(AccessKind::Write, index, self.line));
+        if self.strict {
+            self.initialized.insert(index);
+        }
+        self.registers.set(index, value);
+    }
+
+    /// Reads a register, recording the access and checking that it is
+    /// initialized in strict mode.
+    #[inline]
+    pub(crate) fn read(&self, index: RegisterId) -> Result<T, InterpretError> {
+        if let Some(device) = self.device(index) {
+            self.record(Access::new(AccessKind::Read, index, self.line));
+            return device
+                .borrow_mut()
+                .read(index.0)
+                .map_err(|message| InterpretError::new(DeviceError(message.into()), self.line));
+        }
+        if self.strict && !self.initialized.contains(&index) {
+            return Err(InterpretError::new(UninitializedRead(index.0), self.line));
+        }
+        Ok(self.load(index))
+    }
+
+    /// Returns the device the register is mapped to, if any.
+    #[inline]
+    fn device(&self, index: RegisterId) -> Option<&RefCell<Box<dyn Device<T>>>> {
+        self.devices
+            .iter()
+            .find(|(range, _)| range.contains(&index.0))
+            .map(|(_, device)| device)
     }
 
+    /// Reads a register, recording the access.
     #[inline]
-    fn first(&self) -> i64 {
-        self.registers.get(RegisterId(0))
+    pub(crate) fn load(&self, index: RegisterId) -> T {
+        self.record(Access::new(AccessKind::Read, index, self.line));
+        self.registers.get(index)
     }
 
-    fn get<const N: usize>(&self, index: usize) -> Result<i64, InterpretError> {
+    /// Records an access if the [`Ram`] has a [`MemoryTrace`].
+    #[inline]
+    fn record(&self, access: Access) {
+        if let Some(trace) = &self.trace {
+            trace.record(access);
+        }
+    }
+
+    #[inline]
+    fn segmentation_fault(&self, address: impl ToString) -> InterpretError {
+        InterpretError::new(SegmentationFault(address.to_string().into()), self.line)
+    }
+
+    #[inline]
+    pub(crate) fn set_first(&mut self, value: T) {
+        self.set_register(RegisterId(0), value);
+    }
+
+    #[inline]
+    pub(crate) fn first(&self) -> Result<T, InterpretError> {
+        self.read(RegisterId(0))
+    }
+
+    /// Converts an integer into a word, reporting [`Overflow`] if it does not fit.
+    ///
+    /// [`Overflow`]: crate::errors::InterpretErrorKind::Overflow
+    #[inline]
+    fn word(&self, value: i64) -> Result<T, InterpretError> {
+        T::from_i64(value).ok_or(InterpretError::new(Overflow, self.line))
+    }
+
+    /// Attaches the current line to the error of an arithmetic operation.
+    #[inline]
+    fn arithmetic(&self, result: Result<T, InterpretErrorKind>) -> Result<T, InterpretError> {
+        result.map_err(|kind| InterpretError::new(kind, self.line))
+    }
+
+    fn get<const N: usize>(&self, index: usize) -> Result<T, InterpretError> {
         if N == 0 {
-            return index
-                .try_into()
-                .map_err(|_| InterpretError::new(InvalidLiteral, self.line));
+            return i64::try_from(index)
+                .ok()
+                .and_then(T::from_i64)
+                .ok_or(InterpretError::new(InvalidLiteral, self.line));
         }
 
-        let mut index = index;
+        let mut index = self.address(index)?;
         for _ in 0..N - 1 {
-            index = self
-                .registers
-                .get(RegisterId(index))
-                .try_into()
-                .map_err(|_| InterpretError::new(SegmentationFault, self.line))?;
+            let pointer = self.read(index)?;
+            index = match pointer.to_index() {
+                Some(index) => self.address(index)?,
+                None => return Err(self.segmentation_fault(pointer)),
+            };
         }
-        Ok(self.registers.get(RegisterId(index)))
+        self.read(index)
     }
 }
 
-impl AsRef<Self> for Ram {
+impl<T> AsRef<Self> for Ram<T> {
     fn as_ref(&self) -> &Self {
         self
     }
 }
 
-impl AsMut<Self> for Ram {
+impl<T> AsMut<Self> for Ram<T> {
     fn as_mut(&mut self) -> &mut Self {
         self
     }
 }
 
-impl Debug for Ram {
+impl<T: Word> Debug for Ram<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Ram")
             .field("program", &self.program)
@@ -320,12 +1342,14 @@ impl Debug for Ram {
             .field("line", &self.line)
             .field("halt", &self.halt)
             .field("error", &self.error)
+            .field("extensions", &self.extensions)
+            .field("host_calls", &self.host_calls.keys().collect::<Vec<_>>())
             .finish_non_exhaustive()
     }
 }
 
-impl Iterator for Ram {
-    type Item = RamState;
+impl<T: Word> Iterator for Ram<T> {
+    type Item = RamState<T>;
     fn next(&mut self) -> Option<Self::Item> {
         self.step().ok().and_then(|_| {
             if self.halt {
@@ -337,15 +1361,16 @@ impl Iterator for Ram {
     }
 }
 
-impl FusedIterator for Ram {}
+impl<T: Word> FusedIterator for Ram<T> {}
 
-/// The [`RamState`] struct represents a snapshot of a RAM machine's state.
-#[derive(Default, Debug, Clone)]
-pub struct RamState {
+/// The [`RamState`] struct represents a snapshot of a RAM machine's state,
+/// including its configuration.
+#[derive(Debug, Clone)]
+pub struct RamState<T: Word = i64> {
     /// The program of the RAM machine.
-    pub program: Program,
+    pub program: Program<T>,
     /// The registers of the RAM machine.
-    pub registers: Registers<i64>,
+    pub registers: Registers<T>,
     /// The program counter of the RAM machine.
     pub pc: CodeAddress,
     /// The current line of source code of the RAM machine.
@@ -354,39 +1379,81 @@ pub struct RamState {
     pub halt: bool,
     /// The error of the RAM machine.
     pub error: Option<InterpretError>,
+    /// Where the RAM machine fetches its instructions from.
+    pub mode: ExecutionMode,
+    /// The character encoding used by `READC` and `WRITEC`.
+    pub char_encoding: CharEncoding,
+    /// How `READ` splits the input into values.
+    pub input_mode: InputMode,
+    /// How `WRITE` formats and separates values.
+    pub output_policy: OutputPolicy<T>,
+    /// The pseudo-random number generator of the RAM machine, including its seed.
+    pub rng: Rng,
+    /// The number of instructions executed by the RAM machine.
+    pub clock: u64,
+    /// Whether `ASSERT` instructions are evaluated.
+    pub assertions: bool,
+    /// How arithmetic handles overflow.
+    pub overflow: OverflowMode,
+    /// How `DIV` rounds.
+    pub division: DivisionMode,
+    /// The registers the program may use.
+    pub memory_limit: MemoryLimit,
+    /// The registers written so far that count against the memory limit.
+    pub touched: HashSet<RegisterId>,
+    /// Whether reading an uninitialized register is an error.
+    pub strict: bool,
+    /// The registers initialized so far in strict mode.
+    pub initialized: HashSet<RegisterId>,
+    /// The maximum number of instructions the RAM machine executes.
+    pub step_limit: Option<u64>,
+    /// The input and output tapes, if the RAM machine is in tape I/O mode.
+    pub tapes: Option<Tapes<T>>,
 }
 
-impl From<Ram> for RamState {
-    /// Creates a [`RamState`] instance from a given [`Ram`] instance.
-    fn from(ram: Ram) -> Self {
-        Self {
-            program: ram.program,
-            registers: ram.registers,
-            pc: ram.pc,
-            line: ram.line,
-            halt: ram.halt,
-            error: ram.error,
-        }
+impl<T: Word> Default for RamState<T> {
+    fn default() -> Self {
+        let ram = Ram::from_program(
+            Program::default(),
+            Box::new(std::io::empty()),
+            Box::new(std::io::sink()),
+        );
+        ram.into()
     }
 }
 
-impl From<&Ram> for RamState {
-    /// Creates a [`RamState`] instance from a reference to a [`Ram`] instance.
-    fn from(ram: &Ram) -> Self {
+impl<T: Word> From<Ram<T>> for RamState<T> {
+    /// Creates a [`RamState`] instance from a given [`Ram`] instance.
+    fn from(ram: Ram<T>) -> Self {
         Self {
-            program: ram.program.clone(),
-            registers: ram.registers.clone(),
+            program: ram.program,
+            registers: ram.registers,
             pc: ram.pc,
             line: ram.line,
             halt: ram.halt,
-            error: ram.error.clone(),
+            error: ram.error,
+            mode: ram.mode,
+            char_encoding: ram.char_encoding,
+            input_mode: ram.input_mode,
+            output_policy: ram.output_policy,
+            rng: ram.rng,
+            clock: ram.clock,
+            assertions: ram.assertions,
+            overflow: ram.overflow,
+            division: ram.division,
+            memory_limit: ram.memory_limit,
+            touched: ram.touched,
+            strict: ram.strict,
+            initialized: ram.initialized,
+            step_limit: ram.step_limit,
+            tapes: ram.tapes,
         }
     }
 }
 
-impl From<&mut Ram> for RamState {
+impl<T: Word> From<&Ram<T>> for RamState<T> {
     /// Creates a [`RamState`] instance from a reference to a [`Ram`] instance.
-    fn from(ram: &mut Ram) -> Self {
+    fn from(ram: &Ram<T>) -> Self {
         Self {
             program: ram.program.clone(),
             registers: ram.registers.clone(),
@@ -394,26 +1461,53 @@ impl From<&mut Ram> for RamState {
             line: ram.line,
             halt: ram.halt,
             error: ram.error.clone(),
+            mode: ram.mode,
+            char_encoding: ram.char_encoding,
+            input_mode: ram.input_mode.clone(),
+            output_policy: ram.output_policy.clone(),
+            rng: ram.rng,
+            clock: ram.clock,
+            assertions: ram.assertions,
+            overflow: ram.overflow,
+            division: ram.division,
+            memory_limit: ram.memory_limit,
+            touched: ram.touched.clone(),
+            strict: ram.strict,
+            initialized: ram.initialized.clone(),
+            step_limit: ram.step_limit,
+            tapes: ram.tapes.clone(),
         }
     }
 }
 
-impl AsRef<Self> for RamState {
+impl<T: Word> From<&mut Ram<T>> for RamState<T> {
+    /// Creates a [`RamState`] instance from a reference to a [`Ram`] instance.
+    fn from(ram: &mut Ram<T>) -> Self {
+        Self::from(&*ram)
+    }
+}
+
+impl<T: Word> AsRef<Self> for RamState<T> {
     fn as_ref(&self) -> &Self {
         self
     }
 }
 
-impl AsMut<Self> for RamState {
+impl<T: Word> AsMut<Self> for RamState<T> {
     fn as_mut(&mut self) -> &mut Self {
         self
     }
 }
 
-impl RamState {
+impl<T: Word> RamState<T> {
     /// Creates a new [`Ram`] instance from the given [`RamState`], input reader, and output writer.
+    ///
+    /// Custom instructions, host calls, devices and memory traces are not part
+    /// of the state and must be set again with [`Ram::with_extensions`],
+    /// [`Ram::register_host_call`], [`Ram::register_device`] and
+    /// [`Ram::with_memory_trace`].
     #[must_use]
-    pub fn create_ram(self, reader: Box<dyn BufRead>, writer: Box<dyn Write>) -> Ram {
+    pub fn create_ram(self, reader: Box<dyn BufRead>, writer: Box<dyn Write>) -> Ram<T> {
         Ram {
             program: self.program,
             registers: self.registers,
@@ -421,8 +1515,23 @@ impl RamState {
             line: self.line,
             halt: self.halt,
             error: self.error,
-            reader,
-            writer,
+            mode: self.mode,
+            char_encoding: self.char_encoding,
+            input_mode: self.input_mode,
+            output_policy: self.output_policy,
+            rng: self.rng,
+            clock: self.clock,
+            assertions: self.assertions,
+            overflow: self.overflow,
+            division: self.division,
+            memory_limit: self.memory_limit,
+            touched: self.touched,
+            strict: self.strict,
+            prefilled: false,
+            initialized: self.initialized,
+            step_limit: self.step_limit,
+            tapes: self.tapes,
+            ..Ram::from_program(Program::default(), reader, writer)
         }
     }
 }
@@ -434,6 +1543,9 @@ mod tests {
     use std::rc::Rc;
 
     use super::*;
+    use crate::bigint::BigInt;
+    use crate::errors::InterpretErrorKind::{DivisionByZero, Infinity};
+    use crate::stmt::Comparison;
     use std::io::BufReader;
     use std::io::BufWriter;
 
@@ -584,4 +1696,876 @@ mod tests {
         assert_eq!(ram.eval(Stmt::new(Halt, 6)), Ok(1.into()));
         assert!(ram.halt);
     }
+
+    #[test]
+    fn ram_custom_instruction_test() {
+        use crate::errors::InterpretErrorKind;
+        use crate::extension::{Instruction, Operand, OperandKind};
+        use crate::parser::parse_with_extensions;
+
+        /// `JNEG label` jumps to label if register `0` is negative.
+        struct JumpNegative;
+
+        impl Instruction for JumpNegative {
+            fn mnemonic(&self) -> &str {
+                "JNEG"
+            }
+
+            fn operand(&self) -> OperandKind {
+                OperandKind::Label
+            }
+
+            fn execute(
+                &self,
+                ctx: &mut Context,
+                operand: Operand,
+            ) -> Result<(), InterpretErrorKind> {
+                let Operand::Label(label) = operand else {
+                    return Err(InterpretErrorKind::UnknownLabel);
+                };
+                if ctx.accumulator()? < 0 {
+                    ctx.jump(label)?;
+                }
+                Ok(())
+            }
+        }
+
+        let mut extensions = Extensions::default();
+        extensions.register(JumpNegative);
+
+        let source = "load =-1\njneg negative\nwrite =0\nhalt\nnegative: write =1\nhalt";
+        let program = parse_with_extensions(source, &extensions).unwrap();
+        let reader = BufReader::new(std::io::empty());
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let writer = CustomWriter::new(output.clone());
+
+        let mut ram = Ram::new(program.clone(), Box::new(reader), Box::new(writer))
+            .with_extensions(extensions);
+        ram.run().unwrap();
+        assert_eq!(String::from_utf8(output.borrow().to_vec()).unwrap(), "1");
+
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let mut ram = Ram::new(program, Box::new(reader), Box::new(writer));
+        assert_eq!(ram.run(), Err(InterpretError::new(UnknownInstruction, 2)));
+    }
+
+    #[test]
+    fn ram_host_call_test() {
+        use crate::errors::InterpretErrorKind;
+        use crate::parser::parse;
+
+        let program = parse("load =20\nsys double\nstore 1\nsys fail\nhalt").unwrap();
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let calls = Rc::new(RefCell::new(0));
+        let counter = calls.clone();
+
+        let mut ram = Ram::new(program, Box::new(reader), Box::new(writer))
+            .with_host_call("double", move |ctx| {
+                *counter.borrow_mut() += 1;
+                let value = ctx.accumulator().map_err(|e| e.to_string())?;
+                ctx.set_accumulator(value * 2);
+                Ok(())
+            })
+            .with_host_call("fail", |_| Err("failed".into()));
+
+        assert_eq!(
+            ram.run(),
+            Err(InterpretError::new(
+                InterpretErrorKind::HostCallError("failed".into()),
+                4
+            ))
+        );
+        assert_eq!(ram.get_registers().get(1), 40);
+        assert_eq!(*calls.borrow(), 1);
+
+        let program = parse("sys missing").unwrap();
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let mut ram = Ram::new(program, Box::new(reader), Box::new(writer));
+        assert_eq!(
+            ram.run(),
+            Err(InterpretError::new(UnknownHostCall("missing".into()), 1))
+        );
+    }
+
+    #[test]
+    fn ram_context_io_test() {
+        let program = Program::from_source("write =1\nsys echo\nwrite =3\nhalt").unwrap();
+        let echo = |ctx: &mut Context| -> Result<(), InterpretErrorKind> {
+            let value = ctx.read_value()?;
+            ctx.write_value(value)?;
+            ctx.write_text("|")
+        };
+
+        let reader = BufReader::new("2\n".as_bytes());
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let writer = CustomWriter::new(output.clone());
+        let mut ram = Ram::new(program.clone(), Box::new(reader), Box::new(writer))
+            .with_output_policy(OutputPolicy::separated(","))
+            .with_host_call("echo", move |ctx| echo(ctx).map_err(|e| e.to_string()));
+        ram.run().unwrap();
+        assert_eq!(
+            String::from_utf8(output.borrow().to_vec()).unwrap(),
+            "1,2|3"
+        );
+
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let mut ram = Ram::new(program, Box::new(reader), Box::new(writer))
+            .with_tapes(Tapes::new([2]))
+            .with_host_call("echo", move |ctx| echo(ctx).map_err(|e| e.to_string()));
+        ram.run().unwrap();
+        assert_eq!(ram.get_tapes().unwrap().output, [1, 2, 3]);
+    }
+
+    #[test]
+    fn ram_rasp_test() {
+        let source = "load =3\nloop: jz end\nwrite 0\nsub =1\njmp loop\nend: halt";
+        let program: Program = Program::from_source(source).unwrap();
+        let image = rasp::encode(&program, 10).unwrap();
+
+        let reader = BufReader::new(std::io::empty());
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let writer = CustomWriter::new(output.clone());
+        let mut ram = Ram::from_image(image, CodeAddress(10), Box::new(reader), Box::new(writer));
+
+        ram.run().unwrap();
+        assert_eq!(String::from_utf8(output.borrow().to_vec()).unwrap(), "321");
+        assert_eq!(ram.pc, CodeAddress(22));
+        assert_eq!(RamState::from(&ram).mode, ExecutionMode::Rasp);
+
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let image = [0, 0, 990, 0].into();
+        let mut ram = Ram::from_image(image, CodeAddress(2), Box::new(reader), Box::new(writer));
+        assert_eq!(
+            ram.run(),
+            Err(InterpretError::new(
+                crate::errors::InterpretErrorKind::IllegalInstruction("990".into()),
+                2
+            ))
+        );
+    }
+
+    #[test]
+    fn ram_char_io_test() {
+        let program = Program::from_source(
+            "loop: readc 1\nload 1\nadd =1\njz end\nwritec 1\njmp loop\nend: writec =10\nhalt",
+        )
+        .unwrap();
+        let reader = BufReader::new("héllo, 世界".as_bytes());
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let writer = CustomWriter::new(output.clone());
+
+        let mut ram = Ram::new(program.clone(), Box::new(reader), Box::new(writer));
+        ram.run().unwrap();
+        assert_eq!(
+            String::from_utf8(output.borrow().to_vec()).unwrap(),
+            "héllo, 世界\n"
+        );
+        assert_eq!(ram.get_registers().get(1), EOF);
+
+        let reader = BufReader::new("é".as_bytes());
+        let writer = BufWriter::new(std::io::sink());
+        let mut ram = Ram::new(program, Box::new(reader), Box::new(writer))
+            .with_char_encoding(CharEncoding::Byte);
+        assert_eq!(ram.step(), Ok(()));
+        assert_eq!(ram.get_registers().get(1), 0xC3);
+
+        assert_eq!(
+            ram.eval(Stmt::new(OutputChar(Value::Pure(0x110000)), 7)),
+            Err(InterpretError::new(InvalidCharacter("1114112".into()), 7))
+        );
+    }
+
+    #[test]
+    fn ram_print_test() {
+        let program =
+            Program::from_source("print \"x = \"\nwrite =5\nwrite \"\\n\"\nhalt").unwrap();
+        let reader = BufReader::new(std::io::empty());
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let writer = CustomWriter::new(output.clone());
+
+        let mut ram = Ram::new(program, Box::new(reader), Box::new(writer));
+        ram.run().unwrap();
+        assert_eq!(
+            String::from_utf8(output.borrow().to_vec()).unwrap(),
+            "x = 5\n"
+        );
+    }
+
+    #[test]
+    fn ram_input_mode_test() {
+        let program = Program::from_source("read 1\nread 2\nread 3\nread 4").unwrap();
+        let reader = BufReader::new("3 5\n\n 7,-1;\n".as_bytes());
+        let writer = BufWriter::new(std::io::sink());
+
+        let mut ram = Ram::new(program.clone(), Box::new(reader), Box::new(writer))
+            .with_input_mode(InputMode::Tokens(vec![',', ';']));
+        assert_eq!(ram.run(), Err(InterpretError::new(EndOfProgram, 4)));
+        assert_eq!(ram.get_registers().get(1), 3);
+        assert_eq!(ram.get_registers().get(2), 5);
+        assert_eq!(ram.get_registers().get(3), 7);
+        assert_eq!(ram.get_registers().get(4), -1);
+
+        let reader = BufReader::new("3 x".as_bytes());
+        let writer = BufWriter::new(std::io::sink());
+        let mut ram = Ram::new(program.clone(), Box::new(reader), Box::new(writer))
+            .with_input_mode(InputMode::tokens());
+        assert_eq!(
+            ram.run(),
+            Err(InterpretError::new(InvalidInput("x".into()), 2))
+        );
+
+        let reader = BufReader::new("3 5\n".as_bytes());
+        let writer = BufWriter::new(std::io::sink());
+        let mut ram = Ram::new(program.clone(), Box::new(reader), Box::new(writer));
+        assert_eq!(
+            ram.run(),
+            Err(InterpretError::new(InvalidInput("3 5".into()), 1))
+        );
+
+        let reader = BufReader::new("3\n".as_bytes());
+        let writer = BufWriter::new(std::io::sink());
+        let mut ram = Ram::new(program, Box::new(reader), Box::new(writer));
+        assert_eq!(ram.run(), Err(InterpretError::new(EndOfInput, 2)));
+    }
+
+    #[test]
+    fn ram_output_policy_test() {
+        let run = |policy: OutputPolicy| {
+            let program =
+                Program::from_source("write =1\nwrite =2\nprint \"|\"\nwrite =3\nhalt").unwrap();
+            let reader = BufReader::new(std::io::empty());
+            let output = Rc::new(RefCell::new(Vec::new()));
+            let writer = CustomWriter::new(output.clone());
+            let mut ram =
+                Ram::new(program, Box::new(reader), Box::new(writer)).with_output_policy(policy);
+            ram.run().unwrap();
+            let output = output.borrow().to_vec();
+            String::from_utf8(output).unwrap()
+        };
+
+        assert_eq!(run(OutputPolicy::default()), "12|3");
+        assert_eq!(run(OutputPolicy::newline()), "1\n2|3\n");
+        assert_eq!(
+            run(OutputPolicy::space().with_newline_on_halt(true)),
+            "1 2|3\n"
+        );
+        assert_eq!(
+            run(OutputPolicy::separated(",").with_formatter(|v| format!("<{v}>"))),
+            "<1>,<2>|<3>"
+        );
+    }
+
+    #[test]
+    fn ram_rand_test() {
+        let program =
+            Program::from_source("rand =6\nstore 1\nrand =1000\nstore 2\nrand =0").unwrap();
+        let run = |seed: u64| {
+            let reader = BufReader::new(std::io::empty());
+            let writer = BufWriter::new(std::io::sink());
+            let mut ram =
+                Ram::new(program.clone(), Box::new(reader), Box::new(writer)).with_seed(seed);
+            let result = ram.run();
+            (result, RamState::from(ram))
+        };
+
+        let (result, state) = run(42);
+        assert_eq!(
+            result,
+            Err(InterpretError::new(InvalidRange("0".into()), 5))
+        );
+        assert!((0..6).contains(&state.registers.get(1)));
+        assert!((0..1000).contains(&state.registers.get(2)));
+        assert_eq!(state.rng.seed(), 42);
+
+        let (_, other) = run(42);
+        assert_eq!(state.registers, other.registers);
+    }
+
+    #[test]
+    fn ram_clock_test() {
+        let source = "load =3\nloop: sub =1\njgtz loop\nclock\nhalt";
+        let program = Program::from_source(source).unwrap();
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let mut ram = Ram::new(program, Box::new(reader), Box::new(writer));
+
+        ram.run().unwrap();
+        assert_eq!(ram.get_registers().get(0), 7);
+        assert_eq!(ram.get_clock(), 9);
+        assert_eq!(RamState::from(ram).clock, 9);
+    }
+
+    #[test]
+    fn ram_assert_test() {
+        let source = "load =5\nassert R0 == =5\nassert 0 < =5\nhalt";
+        let program = Program::from_source(source).unwrap();
+        let ram = |assertions: bool| {
+            let reader = BufReader::new(std::io::empty());
+            let writer = BufWriter::new(std::io::sink());
+            Ram::new(program.clone(), Box::new(reader), Box::new(writer))
+                .with_assertions(assertions)
+        };
+
+        assert_eq!(
+            ram(true).run(),
+            Err(InterpretError::new(
+                AssertionFailed {
+                    lhs: "5".into(),
+                    comparison: Comparison::Less,
+                    rhs: "5".into()
+                },
+                3
+            ))
+        );
+        assert_eq!(ram(false).run(), Ok(()));
+    }
+
+    #[test]
+    fn ram_state_config_test() {
+        let program = Program::from_source("load 5\nhalt").unwrap();
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let ram = Ram::new(program, Box::new(reader), Box::new(writer))
+            .with_overflow_mode(OverflowMode::Saturating)
+            .with_division_mode(DivisionMode::Floor)
+            .with_memory_limit(MemoryLimit::unlimited().with_max_index(9))
+            .with_strict(true)
+            .with_step_limit(3)
+            .with_assertions(false)
+            .with_char_encoding(CharEncoding::Byte)
+            .with_input_mode(InputMode::tokens());
+
+        let state = RamState::from(ram);
+        assert_eq!(state.overflow, OverflowMode::Saturating);
+        assert_eq!(state.division, DivisionMode::Floor);
+        assert_eq!(state.step_limit, Some(3));
+        assert!(!state.assertions);
+
+        let ram = state.create_ram(
+            Box::new(BufReader::new(std::io::empty())),
+            Box::new(BufWriter::new(std::io::sink())),
+        );
+        let state = RamState::from(&ram);
+        assert_eq!(state.memory_limit.max_index(), Some(9));
+        assert!(state.strict);
+        assert_eq!(state.char_encoding, CharEncoding::Byte);
+        assert_eq!(state.input_mode, InputMode::tokens());
+
+        let mut ram = state.create_ram(
+            Box::new(BufReader::new(std::io::empty())),
+            Box::new(BufWriter::new(std::io::sink())),
+        );
+        assert_eq!(ram.run(), Err(InterpretError::new(UninitializedRead(5), 1)));
+        assert!(RamState::<i64>::default().assertions);
+    }
+
+    #[test]
+    fn ram_tapes_test() {
+        let source =
+            "readc 1\nloop: read 2\nload 2\njz end\nwritec 2\njmp loop\nend: readc 3\nhalt";
+        let program = Program::from_source(source).unwrap();
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let ram = Ram::new(program, Box::new(reader), Box::new(writer))
+            .with_tapes(Tapes::new([7, 1, 2, 0]));
+
+        let mut ram = RamState::from(ram).create_ram(
+            Box::new(BufReader::new(std::io::empty())),
+            Box::new(BufWriter::new(std::io::sink())),
+        );
+        ram.run().unwrap();
+        assert_eq!(ram.get_registers().get(1), 7);
+        assert_eq!(ram.get_registers().get(3), EOF);
+
+        let state = RamState::from(ram);
+        let tapes = state.tapes.unwrap();
+        assert_eq!(tapes.head, 4);
+        assert_eq!(tapes.output, [1, 2]);
+
+        let program = Program::from_source("read 1").unwrap();
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let mut ram =
+            Ram::new(program, Box::new(reader), Box::new(writer)).with_tapes(Tapes::default());
+        assert_eq!(ram.run(), Err(InterpretError::new(EndOfInput, 1)));
+    }
+
+    #[test]
+    fn ram_real_test() {
+        let run = |source: &str, input: &'static str| {
+            let program = Program::from_source(source).unwrap();
+            let buffer = Rc::new(RefCell::new(Vec::new()));
+            let reader = BufReader::new(input.as_bytes());
+            let writer = CustomWriter::new(Rc::clone(&buffer));
+            let mut ram: Ram<f64> = Ram::from_program(program, Box::new(reader), Box::new(writer))
+                .with_output_policy(OutputPolicy::space());
+            let result = ram.run();
+            let output = String::from_utf8(buffer.borrow().clone()).unwrap();
+            (result, output, ram)
+        };
+
+        let source = "read 1\nload 1\nmult =0.5\nwrite 0\nfloor\nwrite 0\nload =-2.5\ntrunc\nwrite 0\nload =3\nstore 3\nwrite *3\nhalt";
+        let (result, output, _) = run(source, "7.5\n");
+        assert_eq!(result, Ok(()));
+        assert_eq!(output, "3.75 3 -2 3");
+
+        let (result, _, ram) = run("load =1\ndiv =3\nstore 1\nload *1\nhalt", "");
+        let address = (1.0f64 / 3.0).to_string().into();
+        assert_eq!(
+            result,
+            Err(InterpretError::new(SegmentationFault(address), 4))
+        );
+        assert!((ram.get_registers().get(1) - 1.0 / 3.0).abs() < f64::EPSILON);
+
+        let (result, _, _) = run("load =1\ndiv =0\nhalt", "");
+        assert_eq!(result, Err(InterpretError::new(DivisionByZero, 2)));
+
+        let huge = format!("load ={}\nmult 0\nhalt", "9".repeat(300));
+        let (result, _, _) = run(&huge, "");
+        assert_eq!(result, Err(InterpretError::new(Infinity, 2)));
+
+        assert!(Program::<i64>::from_source("load =2.5\nhalt").is_err());
+    }
+
+    #[test]
+    fn ram_bigint_test() {
+        let source = "read 1\nload 1\nmult =100000000000000000000\nwrite 0\nload *1\nhalt";
+        let program = Program::from_source(source).unwrap();
+        let buffer = Rc::new(RefCell::new(Vec::new()));
+        let reader = BufReader::new("-12345678901234567890\n".as_bytes());
+        let writer = CustomWriter::new(Rc::clone(&buffer));
+        let mut ram: Ram<BigInt> = Ram::from_program(program, Box::new(reader), Box::new(writer));
+
+        let address = "-12345678901234567890".into();
+        assert_eq!(
+            ram.run(),
+            Err(InterpretError::new(SegmentationFault(address), 5))
+        );
+        assert_eq!(
+            String::from_utf8(buffer.borrow().clone()).unwrap(),
+            "-1234567890123456789000000000000000000000"
+        );
+    }
+
+    #[test]
+    fn ram_integer_words_test() {
+        fn ram<T: Word>(source: &str, input: &'static str) -> Ram<T> {
+            let program = Program::from_source(source).unwrap();
+            let reader = BufReader::new(input.as_bytes());
+            let writer = BufWriter::new(std::io::sink());
+            Ram::from_program(program, Box::new(reader), Box::new(writer))
+        }
+
+        let mut narrow: Ram<i32> = ram("load =2147483647\nadd =1\nhalt", "");
+        assert_eq!(narrow.run(), Err(InterpretError::new(Overflow, 2)));
+        assert_eq!(narrow.get_registers().get(0), i32::MAX);
+
+        let mut wide: Ram<i128> = ram("load =9223372036854775807\nmult 0\nstore 1\nhalt", "");
+        assert_eq!(wide.run(), Ok(()));
+        assert_eq!(wide.get_registers().get(1), i128::from(i64::MAX).pow(2));
+
+        let mut unsigned: Ram<u64> = ram(
+            "read 1\nload 1\nadd =1\nsub =2\nhalt",
+            "18446744073709551614\n",
+        );
+        assert_eq!(unsigned.run(), Ok(()));
+        assert_eq!(unsigned.get_registers().get(0), u64::MAX - 2);
+
+        let mut unsigned: Ram<u64> = ram("load =0\nsub =1\nhalt", "");
+        assert_eq!(unsigned.run(), Err(InterpretError::new(Overflow, 2)));
+
+        let mut unsigned: Ram<u64> = ram("readc 1\nhalt", "");
+        assert_eq!(unsigned.run(), Err(InterpretError::new(EndOfInput, 1)));
+    }
+
+    #[test]
+    fn ram_overflow_mode_test() {
+        let run = |overflow: OverflowMode| {
+            let source =
+                "load =9223372036854775807\nadd =1\nstore 1\nload =0\nsub =2\nmult 1\nhalt";
+            let program = Program::from_source(source).unwrap();
+            let reader = BufReader::new(std::io::empty());
+            let writer = BufWriter::new(std::io::sink());
+            let mut ram =
+                Ram::new(program, Box::new(reader), Box::new(writer)).with_overflow_mode(overflow);
+            (ram.run(), ram)
+        };
+
+        let (result, _) = run(OverflowMode::Checked);
+        assert_eq!(result, Err(InterpretError::new(Overflow, 2)));
+
+        let (result, ram) = run(OverflowMode::Wrapping);
+        assert_eq!(result, Ok(()));
+        assert_eq!(ram.get_registers().get(1), i64::MIN);
+        assert_eq!(ram.get_registers().get(0), 0);
+
+        let (result, ram) = run(OverflowMode::Saturating);
+        assert_eq!(result, Ok(()));
+        assert_eq!(ram.get_registers().get(1), i64::MAX);
+        assert_eq!(ram.get_registers().get(0), i64::MIN);
+    }
+
+    #[test]
+    fn ram_clock_overflow_mode_test() {
+        let run = |overflow: OverflowMode| {
+            let source = "load =100\nloop: sub =1\njgtz loop\nclock\nhalt";
+            let program = Program::from_source(source).unwrap();
+            let reader = BufReader::new(std::io::empty());
+            let writer = BufWriter::new(std::io::sink());
+            let mut ram: Ram<i8> = Ram::from_program(program, Box::new(reader), Box::new(writer))
+                .with_overflow_mode(overflow);
+            (ram.run(), ram.get_registers().get(0))
+        };
+
+        assert_eq!(
+            run(OverflowMode::Checked),
+            (Err(InterpretError::new(Overflow, 4)), 0)
+        );
+        assert_eq!(run(OverflowMode::Wrapping), (Ok(()), -55));
+        assert_eq!(run(OverflowMode::Saturating), (Ok(()), i8::MAX));
+    }
+
+    #[test]
+    fn ram_division_mode_test() {
+        let run = |division: DivisionMode, source: &str| {
+            let program = Program::from_source(source).unwrap();
+            let reader = BufReader::new(std::io::empty());
+            let writer = BufWriter::new(std::io::sink());
+            let mut ram = Ram::new(program, Box::new(reader), Box::new(writer))
+                .with_division_mode(division)
+                .with_overflow_mode(OverflowMode::Wrapping);
+            (ram.run(), ram.get_registers().get(0))
+        };
+
+        let source = "load =-7\ndiv =2\nhalt";
+        assert_eq!(run(DivisionMode::Truncating, source), (Ok(()), -3));
+        assert_eq!(run(DivisionMode::Floor, source), (Ok(()), -4));
+        let source = "load =-7\ndiv =-2\nhalt";
+        assert_eq!(run(DivisionMode::Euclidean, source), (Ok(()), 4));
+
+        let (result, _) = run(DivisionMode::Floor, "load =1\ndiv =0\nhalt");
+        assert_eq!(result, Err(InterpretError::new(DivisionByZero, 2)));
+    }
+
+    #[test]
+    fn ram_memory_limit_test() {
+        let run = |limit: MemoryLimit, source: &str| {
+            let program = Program::from_source(source).unwrap();
+            let reader = BufReader::new(std::io::empty());
+            let writer = BufWriter::new(std::io::sink());
+            let mut ram =
+                Ram::new(program, Box::new(reader), Box::new(writer)).with_memory_limit(limit);
+            ram.run()
+        };
+        let fault =
+            |address: &str, line| Err(InterpretError::new(SegmentationFault(address.into()), line));
+
+        let limit = MemoryLimit::unlimited().with_max_index(9);
+        assert_eq!(run(limit, "store 9\nload 9\nhalt"), Ok(()));
+        assert_eq!(
+            run(limit, "load =1000000\nstore 1\nstore *1\nhalt"),
+            fault("1000000", 3)
+        );
+        assert_eq!(run(limit, "load 10\nhalt"), fault("10", 1));
+        assert_eq!(
+            run(limit, "load =-1\nstore 1\nload *1\nhalt"),
+            fault("-1", 3)
+        );
+
+        let limit = MemoryLimit::unlimited().with_max_registers(2);
+        let source = "store 1\nstore 2\nstore 1\nstore 0\nload 7\nhalt";
+        assert_eq!(run(limit, source), Ok(()));
+        assert_eq!(run(limit, "store 1\nstore 2\ninc 3\nhalt"), fault("3", 3));
+
+        let program = Program::from_source("sys poke\nhalt").unwrap();
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let mut ram = Ram::new(program, Box::new(reader), Box::new(writer))
+            .with_memory_limit(MemoryLimit::unlimited().with_max_index(9))
+            .with_host_call("poke", |ctx| ctx.set(20, 1).map_err(|e| e.to_string()));
+        let message = "Segmentation Fault at address 20".into();
+        assert_eq!(
+            ram.run(),
+            Err(InterpretError::new(HostCallError(message), 1))
+        );
+    }
+
+    #[test]
+    fn ram_storage_test() {
+        let source = "load =10\nloop: jz end\nstore *0\nsub =1\njmp loop\nend: load 9\nhalt";
+        let bounded = MemoryLimit::unlimited().with_max_index(200);
+        let kinds = [
+            (
+                MemoryLimit::unlimited(),
+                StorageKind::Sparse,
+                StorageKind::Sparse,
+            ),
+            (bounded, StorageKind::Dense, StorageKind::Dense),
+            (
+                MemoryLimit::unlimited(),
+                StorageKind::Dense,
+                StorageKind::hybrid(),
+            ),
+            (
+                MemoryLimit::unlimited(),
+                StorageKind::Hybrid(4),
+                StorageKind::Hybrid(4),
+            ),
+        ];
+        for (limit, kind, expected) in kinds {
+            let program = Program::from_source(source).unwrap();
+            let reader = BufReader::new(std::io::empty());
+            let writer = BufWriter::new(std::io::sink());
+            let mut ram = Ram::new(program, Box::new(reader), Box::new(writer))
+                .with_memory_limit(limit)
+                .with_storage(kind);
+            ram.run().unwrap();
+            assert_eq!(ram.registers.storage_kind(), expected);
+            assert_eq!(ram.registers.get(0), 9);
+            assert_eq!(ram.registers.get(7), 7);
+        }
+
+        let program = Program::from_source("load =1\nstore 1000000000\nhalt").unwrap();
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let mut ram = Ram::new(program, Box::new(reader), Box::new(writer))
+            .with_memory_limit(bounded)
+            .with_storage(StorageKind::Dense)
+            .with_memory_limit(MemoryLimit::unlimited());
+        assert_eq!(ram.registers.storage_kind(), StorageKind::hybrid());
+        ram.run().unwrap();
+        assert_eq!(ram.registers.get(1_000_000_000), 1);
+    }
+
+    #[test]
+    fn ram_memory_trace_test() {
+        use crate::cache::{Cache, CacheConfig};
+        use crate::trace::{Access, AccessKind::*, MemoryTrace};
+
+        let source = "load =7\nstore 2\nstore *2\nadd *2\nhalt";
+        let program = Program::from_source(source).unwrap();
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let trace = MemoryTrace::new();
+        let mut ram =
+            Ram::new(program, Box::new(reader), Box::new(writer)).with_memory_trace(trace.clone());
+        ram.run().unwrap();
+
+        let access = |kind, register, line| Access::new(kind, RegisterId(register), line);
+        assert_eq!(
+            *trace.accesses(),
+            [
+                access(Write, 0, 1),
+                access(Read, 0, 2),
+                access(Write, 2, 2),
+                access(Read, 2, 3),
+                access(Read, 0, 3),
+                access(Write, 7, 3),
+                access(Read, 2, 4),
+                access(Read, 7, 4),
+                access(Read, 0, 4),
+                access(Write, 0, 4),
+            ]
+        );
+
+        let report = Cache::simulate(CacheConfig::new(4, 2, 1), &trace).unwrap();
+        assert_eq!(report.total().misses(), 5);
+        assert_eq!(report.line(3).unwrap().misses(), 1);
+        assert_eq!(report.line(4).unwrap().misses(), 2);
+        assert_eq!(report.line(4).unwrap().hits(), 2);
+
+        let program: Program = Program::from_source("load =1\nhalt").unwrap();
+        let image = rasp::encode(&program, 10).unwrap();
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let trace = MemoryTrace::new();
+        let mut ram = Ram::from_image(image, CodeAddress(10), Box::new(reader), Box::new(writer))
+            .with_memory_trace(trace.clone());
+        ram.run().unwrap();
+        assert_eq!(
+            trace.take(),
+            [
+                access(Fetch, 10, 10),
+                access(Fetch, 11, 10),
+                access(Write, 0, 10),
+                access(Fetch, 12, 12),
+                access(Fetch, 13, 12),
+            ]
+        );
+        assert!(trace.is_empty());
+    }
+
+    #[test]
+    fn ram_strict_test() {
+        let run = |strict: bool, source: &str| {
+            let program = Program::from_source(source).unwrap();
+            let reader = BufReader::new(std::io::empty());
+            let writer = BufWriter::new(std::io::sink());
+            let mut ram = Ram::new(program, Box::new(reader), Box::new(writer)).with_strict(strict);
+            ram.run()
+        };
+        let uninitialized = |index, line| Err(InterpretError::new(UninitializedRead(index), line));
+
+        let program = Program::from_source("halt").unwrap();
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let ram = Ram::new(program, Box::new(reader), Box::new(writer));
+        assert_eq!(ram.registers.entries().count(), PREFILLED_REGISTERS);
+        assert!(ram.with_strict(true).initialized.is_empty());
+
+        let source = "load =3\nstore 1\nload *1\nhalt";
+        assert_eq!(run(false, source), Ok(()));
+        assert_eq!(run(true, source), uninitialized(3, 3));
+        assert_eq!(run(true, "load =0\nstore 1\ninc 1\nload 1\nhalt"), Ok(()));
+        assert_eq!(run(true, "inc 4\nhalt"), uninitialized(4, 1));
+        assert_eq!(run(true, "add =1\nhalt"), uninitialized(0, 1));
+        assert_eq!(run(true, "store 2\nhalt"), uninitialized(0, 1));
+        assert_eq!(run(true, "load =9\nstore *5\nhalt"), uninitialized(5, 2));
+
+        for kind in [StorageKind::Dense, StorageKind::Hybrid(4)] {
+            let program = Program::from_source("load 5\nload 3\nhalt").unwrap();
+            let reader = BufReader::new(std::io::empty());
+            let writer = BufWriter::new(std::io::sink());
+            let mut ram = Ram::new(program, Box::new(reader), Box::new(writer));
+            ram.registers.set(5, 1);
+            let mut ram = ram.with_storage(kind).with_strict(true);
+            assert_eq!(ram.run(), uninitialized(3, 2));
+        }
+
+        let program = Program::from_source("sys peek\nhalt").unwrap();
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let mut ram = Ram::new(program, Box::new(reader), Box::new(writer))
+            .with_strict(true)
+            .with_host_call("peek", |ctx| {
+                ctx.get(2).map(drop).map_err(|e| e.to_string())
+            });
+        let message = "Uninitialized Read of register 2".into();
+        assert_eq!(
+            ram.run(),
+            Err(InterpretError::new(HostCallError(message), 1))
+        );
+
+        let program: Program = Program::from_source("load 11\nhalt").unwrap();
+        let image = rasp::encode(&program, 10).unwrap();
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let mut ram = Ram::from_image(image, CodeAddress(10), Box::new(reader), Box::new(writer))
+            .with_strict(true);
+        assert_eq!(ram.run(), Ok(()));
+        assert_eq!(ram.registers.get(0), 11);
+    }
+
+    #[test]
+    fn ram_device_test() {
+        use crate::mmio;
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let writes = Rc::clone(&log);
+        let device = mmio::from_fns(
+            |address| Ok(address as i64 * 10),
+            move |address, value| {
+                writes.borrow_mut().push((address, value));
+                Ok(())
+            },
+        );
+        let failing = mmio::from_fns(|_| Err("busy".to_string()), |_, _| Ok(()));
+
+        let run = |source: &str| {
+            let program = Program::from_source(source).unwrap();
+            let reader = BufReader::new(std::io::empty());
+            let writer = BufWriter::new(std::io::sink());
+            let mut ram = Ram::new(program, Box::new(reader), Box::new(writer))
+                .with_strict(true)
+                .with_device(10..12, device.clone())
+                .and_then(|ram| ram.with_device(20..21, failing.clone()))
+                .unwrap();
+            ram.run().map(|()| ram.registers)
+        };
+
+        let source = "load 11\nstore 10\ninc 10\nload =10\nstore 5\nload =7\nstore *5\nhalt";
+        let registers = run(source).unwrap();
+        assert_eq!(registers.get(10), 0);
+        assert_eq!(registers.get(5), 10);
+        assert_eq!(*log.borrow(), [(10, 110), (10, 101), (10, 7)]);
+
+        assert_eq!(
+            run("load 20\nhalt"),
+            Err(InterpretError::new(DeviceError("busy".into()), 1))
+        );
+
+        let program = Program::from_source("sys copy\nhalt").unwrap();
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let mut ram = Ram::new(program, Box::new(reader), Box::new(writer))
+            .with_host_call("copy", |ctx| {
+                let value = ctx.get(11).map_err(|e| e.to_string())?;
+                ctx.set(10, value).map_err(|e| e.to_string())
+            })
+            .with_device(10..12, device.clone())
+            .unwrap();
+        assert_eq!(ram.run(), Ok(()));
+        assert_eq!(ram.registers.get(10), 0);
+        assert_eq!(log.borrow().last(), Some(&(10, 110)));
+    }
+
+    #[test]
+    fn ram_device_map_test() {
+        use crate::mmio;
+
+        let device = || mmio::from_fns(|_| Ok(0), |_, _: i64| Ok(()));
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let mut ram = Ram::new(Program::default(), Box::new(reader), Box::new(writer));
+        assert_eq!(ram.register_device(1..5, device()), Ok(()));
+        assert_eq!(
+            ram.register_device(4..8, device()),
+            Err(DeviceMapError::Overlap(4))
+        );
+        assert_eq!(
+            ram.register_device(0..1, device()),
+            Err(DeviceMapError::AccumulatorMapped)
+        );
+        assert_eq!(
+            ram.register_device(9..9, device()),
+            Err(DeviceMapError::EmptyRange)
+        );
+        assert_eq!(ram.register_device(5..8, device()), Ok(()));
+    }
+
+    #[test]
+    fn ram_unary_test() {
+        let reader = BufReader::new(std::io::empty());
+        let writer = BufWriter::new(std::io::sink());
+        let mut ram = Ram::new(Program::default(), Box::new(reader), Box::new(writer));
+
+        ram.registers.set(2, 3);
+        assert_eq!(
+            ram.eval(Stmt::new(Inc(RegisterValue::Direct(3)), 1)),
+            Ok(1.into())
+        );
+        assert_eq!(ram.get_registers().get(3), 1);
+        assert_eq!(
+            ram.eval(Stmt::new(Dec(RegisterValue::Indirect(2)), 2)),
+            Ok(1.into())
+        );
+        assert_eq!(ram.get_registers().get(3), 0);
+
+        ram.registers.set(0, 5);
+        assert_eq!(ram.eval(Stmt::new(Neg, 3)), Ok(1.into()));
+        assert_eq!(ram.get_registers().get(0), -5);
+        assert_eq!(ram.eval(Stmt::new(Abs, 4)), Ok(1.into()));
+        assert_eq!(ram.get_registers().get(0), 5);
+
+        ram.registers.set(0, i64::MIN);
+        assert_eq!(
+            ram.eval(Stmt::new(Abs, 5)),
+            Err(InterpretError::new(Overflow, 5))
+        );
+    }
 }
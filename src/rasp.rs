@@ -0,0 +1,350 @@
+//! The `rasp` module implements the encoding used by the RASP (Random Access
+//! Stored Program) execution mode, in which the program lives in the same
+//! registers as the data and may modify itself.
+//!
+//! Every instruction occupies [`INSTRUCTION_SIZE`] consecutive registers:
+//!
+//! 1. The opcode word `code * 10 + mode`, where `mode` is `0` for a pure value
+//!    (or no operand), `1` for direct and `2` for indirect addressing.
+//! 2. The operand word: the pure value, the register number, or the register
+//!    address of the jump target.
+//!
+//! | Code | Instruction | Code | Instruction |
+//! |------|-------------|------|-------------|
+//! | 0    | `HALT`      | 8    | `JZERO`     |
+//! | 1    | `LOAD`      | 9    | `JGTZ`      |
+//! | 2    | `STORE`     | 10   | `READ`      |
+//! | 3    | `ADD`       | 11   | `WRITE`     |
+//! | 4    | `SUB`       | 12   | `INC`       |
+//! | 5    | `MULT`      | 13   | `DEC`       |
+//! | 6    | `DIV`       | 14   | `NEG`       |
+//! | 7    | `JUMP`      | 15   | `ABS`       |
+//! |      |             | 16   | `READC`     |
+//! |      |             | 17   | `WRITEC`    |
+//! |      |             | 18   | `RAND`      |
+//! |      |             | 19   | `CLOCK`     |
+//! |      |             | 20   | `FLOOR`     |
+//! |      |             | 21   | `TRUNC`     |
+//!
+//! So `LOAD =5` is encoded as `[10, 5]`, and `ADD *3` as `[32, 3]`. Zeroed
+//! memory decodes to `HALT`. String output, assertions, host calls and custom
+//! instructions can not be encoded.
+//!
+//! Encoding and fetching work for any [`Word`] type: a pure operand is stored
+//! as is, every other word of an instruction must be an integer that fits
+//! into `i64`.
+//!
+//! # Examples
+//!
+//! ```
+//! use ramemu::program::{CodeAddress, Program};
+//! use ramemu::ram::Ram;
+//! use ramemu::rasp;
+//! use std::io::BufReader;
+//! use std::io::BufWriter;
+//!
+//! // Overwrites the operand of `add =1` with `10` before executing it.
+//! let source = "
+//!     load =10
+//!     store 107
+//!     load =0
+//!     add =1
+//!     halt
+//! ";
+//! let program: Program = Program::from_source(source).unwrap();
+//! let image = rasp::encode(&program, 100).unwrap();
+//!
+//! let reader = BufReader::new(std::io::empty());
+//! let writer = BufWriter::new(std::io::sink());
+//! let mut ram = Ram::from_image(image, CodeAddress(100), Box::new(reader), Box::new(writer));
+//!
+//! ram.run().unwrap();
+//! assert_eq!(ram.get_registers().get(0), 10);
+//! ```
+
+use rustc_hash::FxHashMap as HashMap;
+
+use crate::errors::InterpretError;
+use crate::errors::InterpretErrorKind::{
+    IllegalInstruction, InvalidRange, NotEncodable, UnknownLabel,
+};
+use crate::program::CodeAddress;
+use crate::program::LabelId;
+use crate::program::Program;
+use crate::registers::RegisterId;
+use crate::registers::Registers;
+use crate::stmt::Op;
+use crate::stmt::Op::{
+    Abs, Add, Clock, Dec, Div, Floor, Halt, Inc, Input, InputChar, Jump, JumpGreatherZero,
+    JumpIfZero, Load, Mult, Neg, Output, OutputChar, Rand, Store, Sub, Trunc,
+};
+use crate::stmt::RegisterValue;
+use crate::stmt::Stmt;
+use crate::stmt::Value;
+use crate::word::Word;
+
+/// Number of registers occupied by one encoded instruction.
+pub const INSTRUCTION_SIZE: usize = 2;
+
+const MODE_PURE: i64 = 0;
+const MODE_DIRECT: i64 = 1;
+const MODE_INDIRECT: i64 = 2;
+
+/// Encodes the program into a memory image, placing the first instruction at
+/// register `base`.
+///
+/// Jump targets are encoded as register addresses of the target instructions.
+/// # Errors
+/// Returns an [`InterpretError`] with the line of the offending statement if
+/// it can not be encoded or refers to an unknown label.
+pub fn encode<T: Word>(program: &Program<T>, base: usize) -> Result<Registers<T>, InterpretError> {
+    let mut image = Registers::default();
+    for (index, stmt) in program.instructions.iter().enumerate() {
+        let [opcode, operand] = encode_op(program, stmt, base)?;
+        let address = instruction_address(base, index)
+            .filter(|address| address.checked_add(1).is_some())
+            .ok_or(InterpretError::new(NotEncodable, stmt.line))?;
+        image.set(address, opcode);
+        image.set(address + 1, operand);
+    }
+    Ok(image)
+}
+
+/// Decodes `len` instructions starting at register `base` back into a [`Program`].
+///
+/// Every jump target gets its own label. Statement lines are the register
+/// addresses of the instructions.
+/// # Errors
+/// Returns an [`InterpretError`] if a word is not a valid instruction, a
+/// jump target is not the address of a decoded instruction or the
+/// instructions do not fit in the address space.
+pub fn decode<T: Word>(
+    image: &Registers<T>,
+    base: usize,
+    len: usize,
+) -> Result<Program<T>, InterpretError> {
+    let mut labels = HashMap::default();
+    let mut instructions = Vec::with_capacity(len);
+
+    for index in 0..len {
+        let address = instruction_address(base, index)
+            .ok_or_else(|| InterpretError::new(InvalidRange(len.to_string().into()), base))?;
+        let mut stmt = fetch(image, CodeAddress(address))?;
+        if let Jump(label) | JumpIfZero(label) | JumpGreatherZero(label) = &mut stmt.op {
+            let target = label
+                .0
+                .checked_sub(base)
+                .filter(|offset| offset % INSTRUCTION_SIZE == 0)
+                .map(|offset| offset / INSTRUCTION_SIZE)
+                .filter(|&target| target <= len)
+                .ok_or(InterpretError::new(UnknownLabel, address))?;
+            *label = LabelId(target);
+            labels.insert(LabelId(target), CodeAddress(target));
+        }
+        instructions.push(stmt);
+    }
+
+    Ok(Program::from(instructions, labels))
+}
+
+/// Decodes the instruction stored at the given register address.
+///
+/// Jump targets are returned as labels whose id is the target register
+/// address, and the statement line is the address of the instruction.
+/// # Errors
+/// Returns [`IllegalInstruction`] if the words do not encode an instruction.
+///
+/// [`IllegalInstruction`]: crate::errors::InterpretErrorKind::IllegalInstruction
+pub fn fetch<T: Word>(
+    image: &Registers<T>,
+    address: CodeAddress,
+) -> Result<Stmt<T>, InterpretError> {
+    let word = image.get(RegisterId(address.0));
+    let illegal = || InterpretError::new(IllegalInstruction(word.to_string().into()), address.0);
+    let opcode = word.to_i64().ok_or_else(illegal)?;
+    let operand = image.get(RegisterId(address.0.checked_add(1).ok_or_else(illegal)?));
+
+    let index = || operand.to_index().ok_or_else(illegal);
+    let register = || match opcode % 10 {
+        MODE_DIRECT => Ok(RegisterValue::Direct(index()?)),
+        MODE_INDIRECT => Ok(RegisterValue::Indirect(index()?)),
+        _ => Err(illegal()),
+    };
+    let value = || match opcode % 10 {
+        MODE_PURE => Ok(Value::Pure(operand.clone())),
+        _ => register().map(Value::Register),
+    };
+    let label = || match opcode % 10 {
+        MODE_PURE => Ok(LabelId(index()?)),
+        _ => Err(illegal()),
+    };
+    let none = |op: Op<T>| match opcode % 10 {
+        MODE_PURE => Ok(op),
+        _ => Err(illegal()),
+    };
+
+    let op = match opcode / 10 {
+        0 if opcode >= 0 => none(Halt)?,
+        1 => Load(value()?),
+        2 => Store(register()?),
+        3 => Add(value()?),
+        4 => Sub(value()?),
+        5 => Mult(value()?),
+        6 => Div(value()?),
+        7 => Jump(label()?),
+        8 => JumpIfZero(label()?),
+        9 => JumpGreatherZero(label()?),
+        10 => Input(register()?),
+        11 => Output(value()?),
+        12 => Inc(register()?),
+        13 => Dec(register()?),
+        14 => none(Neg)?,
+        15 => none(Abs)?,
+        16 => InputChar(register()?),
+        17 => OutputChar(value()?),
+        18 => Rand(value()?),
+        19 => none(Clock)?,
+        20 => none(Floor)?,
+        21 => none(Trunc)?,
+        _ => return Err(illegal()),
+    };
+
+    Ok(Stmt::new(op, address.0))
+}
+
+/// Returns the register address of the instruction at `index`, or `None` if
+/// it does not fit in a `usize`.
+fn instruction_address(base: usize, index: usize) -> Option<usize> {
+    index
+        .checked_mul(INSTRUCTION_SIZE)
+        .and_then(|offset| base.checked_add(offset))
+}
+
+fn encode_op<T: Word>(
+    program: &Program<T>,
+    stmt: &Stmt<T>,
+    base: usize,
+) -> Result<[T; 2], InterpretError> {
+    let word = |value: i64| T::from_i64(value).ok_or(InterpretError::new(NotEncodable, stmt.line));
+    let index = |value: usize| {
+        i64::try_from(value)
+            .map_err(|_| InterpretError::new(NotEncodable, stmt.line))
+            .and_then(word)
+    };
+    let register_value = |register: RegisterValue| match register {
+        RegisterValue::Direct(i) => index(i).map(|w| (MODE_DIRECT, w)),
+        RegisterValue::Indirect(i) => index(i).map(|w| (MODE_INDIRECT, w)),
+    };
+    let value = |value: &Value<T>| match value {
+        Value::Pure(value) => Ok((MODE_PURE, value.clone())),
+        Value::Register(register) => register_value(*register),
+    };
+    let label = |label: LabelId| {
+        let target = program
+            .decode_label(label)
+            .ok_or(InterpretError::new(UnknownLabel, stmt.line))?;
+        instruction_address(base, target.0)
+            .ok_or(InterpretError::new(NotEncodable, stmt.line))
+            .and_then(index)
+            .map(|w| (MODE_PURE, w))
+    };
+    let none = || (MODE_PURE, T::default());
+
+    let (code, (mode, operand)) = match &stmt.op {
+        Halt => (0, none()),
+        Load(v) => (1, value(v)?),
+        Store(r) => (2, register_value(*r)?),
+        Add(v) => (3, value(v)?),
+        Sub(v) => (4, value(v)?),
+        Mult(v) => (5, value(v)?),
+        Div(v) => (6, value(v)?),
+        Jump(l) => (7, label(*l)?),
+        JumpIfZero(l) => (8, label(*l)?),
+        JumpGreatherZero(l) => (9, label(*l)?),
+        Input(r) => (10, register_value(*r)?),
+        Output(v) => (11, value(v)?),
+        Inc(r) => (12, register_value(*r)?),
+        Dec(r) => (13, register_value(*r)?),
+        Neg => (14, none()),
+        Abs => (15, none()),
+        InputChar(r) => (16, register_value(*r)?),
+        OutputChar(v) => (17, value(v)?),
+        Rand(v) => (18, value(v)?),
+        Clock => (19, none()),
+        Floor => (20, none()),
+        Trunc => (21, none()),
+        Op::Print(_) | Op::Sys(_) | Op::Assert(..) | Op::Custom(..) => {
+            return Err(InterpretError::new(NotEncodable, stmt.line))
+        }
+    };
+
+    Ok([word(code * 10 + mode)?, operand])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let source = "read 1\nloop: load 1\njz end\nsub =1\nstore *2\njmp loop\nend: halt";
+        let program: Program = Program::from_source(source).unwrap();
+        let image = encode(&program, 10).unwrap();
+
+        assert_eq!(image.get(10), 101);
+        assert_eq!(image.get(11), 1);
+        assert_eq!(image.get(14), 80);
+        assert_eq!(image.get(15), 22);
+        assert_eq!(image.get(18), 22);
+        assert_eq!(image.get(19), 2);
+
+        let decoded = decode(&image, 10, program.instructions.len()).unwrap();
+        let ops = |program: &Program| {
+            program
+                .instructions
+                .iter()
+                .enumerate()
+                .map(|(i, stmt)| match stmt.op {
+                    Jump(l) | JumpIfZero(l) => (i, program.decode_label(l)),
+                    _ => (i, None),
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ops(&decoded), ops(&program));
+        assert_eq!(decoded.instructions[3].op, Sub(Value::Pure(1)));
+    }
+
+    #[test]
+    fn test_illegal_instruction() {
+        let image: Registers<i64> = [33, 1, 143, 0, 160, 0].into();
+        assert!(fetch(&image, CodeAddress(0)).is_err());
+        assert!(fetch(&image, CodeAddress(2)).is_err());
+        assert_eq!(
+            fetch(&image, CodeAddress(4)),
+            Err(InterpretError::new(IllegalInstruction("160".into()), 4))
+        );
+    }
+
+    #[test]
+    fn test_not_encodable() {
+        let program: Program = Program::from_source("load =1\nsys log").unwrap();
+        assert_eq!(
+            encode(&program, 0),
+            Err(InterpretError::new(NotEncodable, 2))
+        );
+    }
+
+    #[test]
+    fn test_base_overflow() {
+        let program: Program = Program::from_source("load =1\nhalt").unwrap();
+        assert_eq!(
+            encode(&program, usize::MAX - 1),
+            Err(InterpretError::new(NotEncodable, 2))
+        );
+        let program: Program = Program::from_source("a: jump a").unwrap();
+        assert_eq!(
+            encode(&program, usize::MAX),
+            Err(InterpretError::new(NotEncodable, 1))
+        );
+    }
+}
@@ -19,10 +19,297 @@
 //!
 //! This module is typically used in combination with other components of an
 //! assembly language interpreter or compiler.
+//!
+//! The values are kept in one of several backends, selected with
+//! [`StorageKind`]:
+//!
+//! - [`StorageKind::Sparse`], a hash map, only allocates the registers that
+//!   are written, however far apart they are. It is the default.
+//! - [`StorageKind::Dense`], a vector, avoids hashing but allocates every
+//!   register up to the highest one written.
+//! - [`StorageKind::Hybrid`] keeps a dense prefix of low registers and a sparse
+//!   tail, so huge addresses do not allocate the registers below them.
+//!
+//! Dense and hybrid storage avoid hashing on every access, which speeds up
+//! tight loops over low registers, see `cargo bench --bench storage`.
+//!
+//! ```
+//! use ramemu::registers::{Registers, StorageKind};
+//!
+//! let mut registers = Registers::new(StorageKind::hybrid());
+//! registers.set(3, 42);
+//! registers.set(1_000_000_000, 7);
+//! assert_eq!(registers.get(3), 42);
+//! assert_eq!(registers.storage_kind(), StorageKind::Hybrid(1024));
+//! ```
+//!
+//! The kind is chosen at runtime by [`AnyStorage`], the default backend of
+//! [`Registers`]. A backend can also be fixed at compile time, or replaced by
+//! a custom one, by implementing [`Storage`]:
+//!
+//! ```
+//! use ramemu::registers::{DenseStorage, Registers};
+//!
+//! let mut registers = Registers::from_storage(DenseStorage::default());
+//! registers.set(3, 42);
+//! assert_eq!(registers.get(3), 42);
+//! ```
 
 use rustc_hash::FxHashMap as HashMap;
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 use std::iter::FromIterator;
+use std::marker::PhantomData;
+
+use crate::word::Word;
+
+/// A backend that stores the values of registers.
+///
+/// Registers that were never set are not stored, [`Registers`] reads them as
+/// the default value.
+pub trait Storage<T>: Default + Clone {
+    /// Returns the stored value of the register at the given index.
+    ///
+    /// If the register has not been set, may return `None`.
+    fn get(&self, index: usize) -> Option<&T>;
+
+    /// Stores the value of the register at the given index.
+    fn set(&mut self, index: usize, value: T);
+
+    /// Iterates over the stored registers in no particular order.
+    fn entries<'a>(&'a self) -> impl Iterator<Item = (usize, &'a T)>
+    where
+        T: 'a;
+}
+
+/// Stores registers in a hash map.
+#[derive(Debug, Clone)]
+pub struct SparseStorage<T> {
+    values: HashMap<usize, T>,
+}
+
+impl<T> Default for SparseStorage<T> {
+    fn default() -> Self {
+        Self {
+            values: HashMap::default(),
+        }
+    }
+}
+
+impl<T: Clone> Storage<T> for SparseStorage<T> {
+    #[inline]
+    fn get(&self, index: usize) -> Option<&T> {
+        self.values.get(&index)
+    }
+
+    #[inline]
+    fn set(&mut self, index: usize, value: T) {
+        self.values.insert(index, value);
+    }
+
+    fn entries<'a>(&'a self) -> impl Iterator<Item = (usize, &'a T)>
+    where
+        T: 'a,
+    {
+        self.values.iter().map(|(&index, value)| (index, value))
+    }
+}
+
+/// Stores registers in a vector indexed by the register number.
+///
+/// Setting a register allocates an empty slot for every register below it,
+/// so a store to a huge index allocates gigabytes. Only use it when the
+/// indices are bounded, e.g. by [`MemoryLimit::with_max_index`].
+///
+/// [`MemoryLimit::with_max_index`]: crate::ram::MemoryLimit::with_max_index
+#[derive(Debug, Clone)]
+pub struct DenseStorage<T> {
+    values: Vec<Option<T>>,
+}
+
+impl<T> Default for DenseStorage<T> {
+    fn default() -> Self {
+        Self { values: Vec::new() }
+    }
+}
+
+impl<T: Clone> Storage<T> for DenseStorage<T> {
+    #[inline]
+    fn get(&self, index: usize) -> Option<&T> {
+        self.values.get(index).and_then(Option::as_ref)
+    }
+
+    #[inline]
+    fn set(&mut self, index: usize, value: T) {
+        if index >= self.values.len() {
+            self.values.resize_with(index + 1, || None);
+        }
+        self.values[index] = Some(value);
+    }
+
+    fn entries<'a>(&'a self) -> impl Iterator<Item = (usize, &'a T)>
+    where
+        T: 'a,
+    {
+        self.values
+            .iter()
+            .enumerate()
+            .filter_map(|(index, value)| value.as_ref().map(|value| (index, value)))
+    }
+}
+
+/// Stores the registers below a fixed length densely and the rest sparsely.
+#[derive(Debug, Clone)]
+pub struct HybridStorage<T> {
+    dense: DenseStorage<T>,
+    sparse: SparseStorage<T>,
+    dense_len: usize,
+}
+
+impl<T> HybridStorage<T> {
+    /// Creates a storage that keeps registers `0..dense_len` densely.
+    #[must_use]
+    pub fn new(dense_len: usize) -> Self {
+        Self {
+            dense: DenseStorage::default(),
+            sparse: SparseStorage::default(),
+            dense_len,
+        }
+    }
+}
+
+impl<T> Default for HybridStorage<T> {
+    fn default() -> Self {
+        Self::new(StorageKind::DEFAULT_DENSE_LEN)
+    }
+}
+
+impl<T: Clone + Default> Storage<T> for HybridStorage<T> {
+    #[inline]
+    fn get(&self, index: usize) -> Option<&T> {
+        if index < self.dense_len {
+            self.dense.get(index)
+        } else {
+            self.sparse.get(index)
+        }
+    }
+
+    #[inline]
+    fn set(&mut self, index: usize, value: T) {
+        if index < self.dense_len {
+            self.dense.set(index, value);
+        } else {
+            self.sparse.set(index, value);
+        }
+    }
+
+    fn entries<'a>(&'a self) -> impl Iterator<Item = (usize, &'a T)>
+    where
+        T: 'a,
+    {
+        self.dense.entries().chain(self.sparse.entries())
+    }
+}
+
+/// Selects how [`Registers`] store their values.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum StorageKind {
+    /// Stores registers in a hash map.
+    #[default]
+    Sparse,
+    /// Stores registers in a vector indexed by the register number.
+    ///
+    /// Allocates every register up to the highest one written, see
+    /// [`DenseStorage`].
+    Dense,
+    /// Stores the registers below the given length densely and the rest sparsely.
+    Hybrid(usize),
+}
+
+impl StorageKind {
+    /// The length of the dense prefix of [`StorageKind::hybrid`].
+    pub const DEFAULT_DENSE_LEN: usize = 1024;
+
+    /// Returns a hybrid storage with a dense prefix of [`Self::DEFAULT_DENSE_LEN`] registers.
+    #[must_use]
+    pub fn hybrid() -> Self {
+        Self::Hybrid(Self::DEFAULT_DENSE_LEN)
+    }
+}
+
+/// Stores registers in the backend selected by a [`StorageKind`] at runtime.
+///
+/// This is the default backend of [`Registers`] and the one [`Ram`] uses.
+///
+/// [`Ram`]: crate::ram::Ram
+#[derive(Debug, Clone)]
+pub enum AnyStorage<T> {
+    /// See [`StorageKind::Sparse`].
+    Sparse(SparseStorage<T>),
+    /// See [`StorageKind::Dense`].
+    Dense(DenseStorage<T>),
+    /// See [`StorageKind::Hybrid`].
+    Hybrid(HybridStorage<T>),
+}
+
+impl<T> AnyStorage<T> {
+    /// Creates an empty storage of the given kind.
+    #[must_use]
+    pub fn new(kind: StorageKind) -> Self {
+        match kind {
+            StorageKind::Sparse => Self::Sparse(SparseStorage::default()),
+            StorageKind::Dense => Self::Dense(DenseStorage::default()),
+            StorageKind::Hybrid(dense_len) => Self::Hybrid(HybridStorage::new(dense_len)),
+        }
+    }
+
+    /// Returns the kind of the storage.
+    #[must_use]
+    pub fn kind(&self) -> StorageKind {
+        match self {
+            Self::Sparse(_) => StorageKind::Sparse,
+            Self::Dense(_) => StorageKind::Dense,
+            Self::Hybrid(storage) => StorageKind::Hybrid(storage.dense_len),
+        }
+    }
+}
+
+impl<T> Default for AnyStorage<T> {
+    fn default() -> Self {
+        Self::new(StorageKind::default())
+    }
+}
+
+impl<T: Clone + Default> Storage<T> for AnyStorage<T> {
+    #[inline]
+    fn get(&self, index: usize) -> Option<&T> {
+        match self {
+            Self::Sparse(storage) => storage.get(index),
+            Self::Dense(storage) => storage.get(index),
+            Self::Hybrid(storage) => storage.get(index),
+        }
+    }
+
+    #[inline]
+    fn set(&mut self, index: usize, value: T) {
+        match self {
+            Self::Sparse(storage) => storage.set(index, value),
+            Self::Dense(storage) => storage.set(index, value),
+            Self::Hybrid(storage) => storage.set(index, value),
+        }
+    }
+
+    fn entries<'a>(&'a self) -> impl Iterator<Item = (usize, &'a T)>
+    where
+        T: 'a,
+    {
+        let entries: Box<dyn Iterator<Item = (usize, &'a T)> + 'a> = match self {
+            Self::Sparse(storage) => Box::new(storage.entries()),
+            Self::Dense(storage) => Box::new(storage.entries()),
+            Self::Hybrid(storage) => Box::new(storage.entries()),
+        };
+        entries
+    }
+}
 
 /// Represents an infinite set of registers.
 ///
@@ -30,6 +317,8 @@ use std::iter::FromIterator;
 /// of registers. This allows for efficient access to the registers and dynamic
 /// growth of the register set.
 ///
+/// The values are kept in a [`Storage`] backend `S`, [`AnyStorage`] by default.
+///
 /// # Examples
 ///
 /// ```
@@ -43,10 +332,54 @@ use std::iter::FromIterator;
 /// assert_eq!(registers.get(RegisterId(0)), 42);
 /// assert_eq!(registers.get(RegisterId(1)), 24);
 /// ```
-#[derive(Default, Clone, PartialEq, Eq)]
-#[repr(transparent)]
-pub struct Registers<T> {
-    registers: HashMap<RegisterId, T>,
+#[derive(Clone)]
+pub struct Registers<T, S = AnyStorage<T>> {
+    storage: S,
+    value: PhantomData<T>,
+}
+
+impl<T> Default for Registers<T> {
+    fn default() -> Self {
+        Self::new(StorageKind::default())
+    }
+}
+
+impl<T, S> Registers<T, S> {
+    /// Creates registers kept in the given storage.
+    #[inline]
+    #[must_use]
+    pub fn from_storage(storage: S) -> Self {
+        Self {
+            storage,
+            value: PhantomData,
+        }
+    }
+
+    /// Returns the storage the registers are kept in.
+    #[inline]
+    #[must_use]
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+}
+
+impl<T> Registers<T> {
+    /// Creates empty registers kept in the given kind of storage.
+    #[must_use]
+    pub fn new(kind: StorageKind) -> Self {
+        Self::from_storage(AnyStorage::new(kind))
+    }
+
+    /// Returns the kind of storage the registers are kept in.
+    #[must_use]
+    pub fn storage_kind(&self) -> StorageKind {
+        self.storage.kind()
+    }
+
+    /// Resets every register to the default value, keeping the kind of storage.
+    pub fn clear(&mut self) {
+        self.storage = AnyStorage::new(self.storage_kind());
+    }
 }
 
 /// Represents a register id.
@@ -59,7 +392,7 @@ impl From<usize> for RegisterId {
     }
 }
 
-impl<T: Clone + Default> Registers<T> {
+impl<T: Clone + Default, S: Storage<T>> Registers<T, S> {
     /// Returns the value of the register at the given index.
     ///
     /// If the register has not been set, the default value for the value type `T` is returned.
@@ -75,8 +408,8 @@ impl<T: Clone + Default> Registers<T> {
     /// ```
     #[inline]
     pub fn get(&self, index: impl Into<RegisterId>) -> T {
-        self.registers
-            .get(&index.into())
+        self.storage
+            .get(index.into().0)
             .cloned()
             .unwrap_or_default()
     }
@@ -94,62 +427,252 @@ impl<T: Clone + Default> Registers<T> {
     /// ```
     #[inline]
     pub fn set(&mut self, index: impl Into<RegisterId>, value: T) {
-        self.registers.insert(index.into(), value);
+        self.storage.set(index.into().0, value);
+    }
+
+    /// Returns the highest index of a register that has been set, even if it
+    /// was set to the default value.
+    #[must_use]
+    pub fn highest_index(&self) -> Option<RegisterId> {
+        self.entries().map(|(index, _)| index).max()
+    }
+
+    /// Iterates over the stored registers in no particular order.
+    pub(crate) fn entries(&self) -> impl Iterator<Item = (RegisterId, &T)> {
+        self.storage
+            .entries()
+            .map(|(index, value)| (RegisterId(index), value))
     }
 }
 
-impl<T: Clone + Default + Debug> Debug for Registers<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let max_index = self.registers.keys().max().unwrap_or(&0.into()).0;
-        let vec: Vec<T> = (0..=max_index)
-            .map(|i| self.registers.get(&i.into()).cloned().unwrap_or_default())
+impl<T: Clone + Default> Registers<T> {
+    /// Moves the registers into the given kind of storage.
+    #[must_use]
+    pub fn with_storage(self, kind: StorageKind) -> Self {
+        let mut registers = Self::new(kind);
+        for (index, value) in self.entries() {
+            registers.set(index, value.clone());
+        }
+        registers
+    }
+}
+
+impl<T: Clone + Default + PartialEq, S: Storage<T>> Registers<T, S> {
+    /// Iterates over the registers holding a non-default value, in ascending
+    /// order of their index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ramemu::registers::{RegisterId, Registers};
+    ///
+    /// let registers: Registers<i64> = [0, 7, 0, 9].into();
+    /// let entries: Vec<_> = registers.iter().collect();
+    /// assert_eq!(entries, [(RegisterId(1), &7), (RegisterId(3), &9)]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (RegisterId, &T)> {
+        let default = T::default();
+        let mut entries: Vec<_> = self
+            .entries()
+            .filter(|(_, value)| **value != default)
+            .collect();
+        entries.sort_unstable_by_key(|(index, _)| *index);
+        entries.into_iter()
+    }
+
+    /// Compares the registers with `other` and returns every register whose
+    /// value differs, in ascending order of their index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ramemu::registers::{RegisterDiff, RegisterId, Registers};
+    ///
+    /// let before: Registers<i64> = [1, 2, 3].into();
+    /// let after: Registers<i64> = [1, 5].into();
+    /// assert_eq!(
+    ///     before.diff(&after),
+    ///     [
+    ///         RegisterDiff { index: RegisterId(1), left: 2, right: 5 },
+    ///         RegisterDiff { index: RegisterId(2), left: 3, right: 0 },
+    ///     ]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> Vec<RegisterDiff<T>> {
+        let mut indices: Vec<_> = self
+            .entries()
+            .chain(other.entries())
+            .map(|(index, _)| index)
             .collect();
-        Debug::fmt(&vec, f)
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+            .into_iter()
+            .filter_map(|index| {
+                let (left, right) = (self.get(index), other.get(index));
+                (left != right).then_some(RegisterDiff { index, left, right })
+            })
+            .collect()
+    }
+
+    /// Returns a sparse, human readable view of the registers.
+    ///
+    /// Only registers holding a non-default value are shown, one per line,
+    /// and runs of consecutive registers holding the same value are collapsed
+    /// into a range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ramemu::registers::Registers;
+    ///
+    /// let mut registers: Registers<i64> = [42, 5, 5, 5].into();
+    /// registers.set(1_000_000_000, -255);
+    ///
+    /// assert_eq!(
+    ///     registers.display().to_string(),
+    ///     "R0 = 42\nR1..=R3 = 5\nR1000000000 = -255\n"
+    /// );
+    /// assert_eq!(
+    ///     registers.display().with_hex(true).to_string(),
+    ///     "R0 = 0x2a\nR1..=R3 = 0x5\nR1000000000 = -0xff\n"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn display(&self) -> RegistersDisplay<'_, T, S> {
+        RegistersDisplay {
+            registers: self,
+            hex: false,
+        }
     }
 }
 
-impl<T> FromIterator<T> for Registers<T> {
+/// A register whose value differs between two register files, see
+/// [`Registers::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterDiff<T> {
+    /// The index of the register.
+    pub index: RegisterId,
+    /// The value in the registers `diff` was called on.
+    pub left: T,
+    /// The value in the other registers.
+    pub right: T,
+}
+
+/// Displays the registers sparsely, see [`Registers::display`].
+pub struct RegistersDisplay<'a, T, S = AnyStorage<T>> {
+    registers: &'a Registers<T, S>,
+    hex: bool,
+}
+
+impl<T, S> RegistersDisplay<'_, T, S> {
+    /// Shows integer values in hexadecimal, other values are shown as usual.
     #[inline]
-    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        Self {
-            registers: iter
-                .into_iter()
-                .enumerate()
-                .map(|(i, v)| (RegisterId(i), v))
-                .collect(),
+    #[must_use]
+    pub fn with_hex(mut self, hex: bool) -> Self {
+        self.hex = hex;
+        self
+    }
+}
+
+impl<T: Word, S> RegistersDisplay<'_, T, S> {
+    fn fmt_value(&self, f: &mut std::fmt::Formatter<'_>, value: &T) -> std::fmt::Result {
+        match value.to_hex().filter(|_| self.hex) {
+            Some(hex) => f.write_str(&hex),
+            None => write!(f, "{value}"),
+        }
+    }
+}
+
+impl<T: Word, S: Storage<T>> Display for RegistersDisplay<'_, T, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut entries = self.registers.iter().peekable();
+        while let Some((start, value)) = entries.next() {
+            let mut end = start;
+            while entries
+                .next_if(|(index, next)| index.0 == end.0 + 1 && *next == value)
+                .is_some()
+            {
+                end = RegisterId(end.0 + 1);
+            }
+            if start == end {
+                write!(f, "R{} = ", start.0)?;
+            } else {
+                write!(f, "R{}..=R{} = ", start.0, end.0)?;
+            }
+            self.fmt_value(f, value)?;
+            writeln!(f)?;
         }
+        Ok(())
+    }
+}
+
+impl<T: Clone + Default + PartialEq, S: Storage<T>> PartialEq for Registers<T, S> {
+    /// Registers are equal if every register holds the same value, no matter
+    /// how they are stored.
+    fn eq(&self, other: &Self) -> bool {
+        self.entries()
+            .all(|(index, value)| *value == other.get(index))
+            && other
+                .entries()
+                .all(|(index, value)| *value == self.get(index))
     }
 }
 
-impl<T> AsRef<Self> for Registers<T> {
+impl<T: Clone + Default + Eq, S: Storage<T>> Eq for Registers<T, S> {}
+
+impl<T: Clone + Default + PartialEq + Debug, S: Storage<T>> Debug for Registers<T, S> {
+    /// Formats the registers holding a non-default value as a map from their
+    /// index to the value.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(self.iter().map(|(index, value)| (index.0, value)))
+            .finish()
+    }
+}
+
+impl<T: Clone + Default, S: Storage<T>> FromIterator<T> for Registers<T, S> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        iter.into_iter()
+            .enumerate()
+            .map(|(i, v)| (RegisterId(i), v))
+            .collect()
+    }
+}
+
+impl<T, S> AsRef<Self> for Registers<T, S> {
     fn as_ref(&self) -> &Self {
         self
     }
 }
 
-impl<T> AsMut<Self> for Registers<T> {
+impl<T, S> AsMut<Self> for Registers<T, S> {
     fn as_mut(&mut self) -> &mut Self {
         self
     }
 }
 
-impl<T> FromIterator<(RegisterId, T)> for Registers<T> {
+impl<T: Clone + Default, S: Storage<T>> FromIterator<(RegisterId, T)> for Registers<T, S> {
     #[inline]
     fn from_iter<I: IntoIterator<Item = (RegisterId, T)>>(iter: I) -> Self {
-        Self {
-            registers: iter.into_iter().collect(),
+        let mut registers = Self::from_storage(S::default());
+        for (index, value) in iter {
+            registers.set(index, value);
         }
+        registers
     }
 }
 
-impl<T, const N: usize> From<[T; N]> for Registers<T> {
+impl<T: Clone + Default, const N: usize> From<[T; N]> for Registers<T> {
     #[inline]
     fn from(value: [T; N]) -> Self {
         Self::from_iter(value)
     }
 }
 
-impl<T: Clone> From<&[T]> for Registers<T> {
+impl<T: Clone + Default> From<&[T]> for Registers<T> {
     fn from(value: &[T]) -> Self {
         value.iter().cloned().collect()
     }
@@ -157,7 +680,9 @@ impl<T: Clone> From<&[T]> for Registers<T> {
 
 #[cfg(test)]
 mod tests {
-    use super::Registers;
+    use super::{DenseStorage, RegisterDiff, RegisterId, Registers, Storage, StorageKind};
+    use crate::bigint::BigInt;
+    use crate::word::Word;
 
     #[test]
     fn test_set_and_get() {
@@ -217,6 +742,71 @@ mod tests {
         assert_eq!(registers.get(2), 30);
     }
 
+    #[test]
+    fn test_storage_kinds() {
+        let kinds = [
+            StorageKind::Sparse,
+            StorageKind::Dense,
+            StorageKind::Hybrid(4),
+        ];
+        for kind in kinds {
+            let mut registers: Registers<i64> = Registers::new(kind);
+            registers.set(2, 20);
+            registers.set(3, 30);
+            registers.set(100, 7);
+
+            assert_eq!(registers.storage_kind(), kind);
+            assert_eq!(registers.get(2), 20);
+            assert_eq!(registers.get(3), 30);
+            assert_eq!(registers.get(100), 7);
+            assert_eq!(registers.get(50), 0);
+
+            let converted = registers.clone().with_storage(StorageKind::Sparse);
+            assert_eq!(converted.storage_kind(), StorageKind::Sparse);
+            assert_eq!(converted, registers);
+        }
+
+        let mut zeroed: Registers<i64> = Registers::new(StorageKind::Dense);
+        zeroed.set(5, 0);
+        assert_eq!(zeroed, Registers::default());
+    }
+
+    #[test]
+    fn test_custom_storage() {
+        #[derive(Default, Clone)]
+        struct ListStorage(Vec<(usize, i64)>);
+
+        impl Storage<i64> for ListStorage {
+            fn get(&self, index: usize) -> Option<&i64> {
+                self.0.iter().find(|(i, _)| *i == index).map(|(_, v)| v)
+            }
+
+            fn set(&mut self, index: usize, value: i64) {
+                self.0.retain(|(i, _)| *i != index);
+                self.0.push((index, value));
+            }
+
+            fn entries<'a>(&'a self) -> impl Iterator<Item = (usize, &'a i64)>
+            where
+                i64: 'a,
+            {
+                self.0.iter().map(|(i, v)| (*i, v))
+            }
+        }
+
+        let mut registers = Registers::from_storage(ListStorage::default());
+        registers.set(3, 30);
+        registers.set(1, 10);
+        registers.set(3, 31);
+        assert_eq!(registers.get(3), 31);
+        assert_eq!(registers.get(2), 0);
+        assert_eq!(registers.storage().0.len(), 2);
+        assert_eq!(registers.display().to_string(), "R1 = 10\nR3 = 31\n");
+
+        let dense: Registers<i64, DenseStorage<i64>> = [1, 2].into_iter().collect();
+        assert_eq!(format!("{dense:?}"), "{0: 1, 1: 2}");
+    }
+
     #[test]
     fn test_debug_fmt() {
         let mut registers: Registers<u32> = Registers::default();
@@ -226,6 +816,87 @@ mod tests {
         registers.set(2, 10);
 
         let debug_output = format!("{registers:?}");
-        assert_eq!(debug_output, "[42, 24, 10]");
+        assert_eq!(debug_output, "{0: 42, 1: 24, 2: 10}");
+
+        registers.set(1_000_000_000, 7);
+        registers.set(1, 0);
+        let debug_output = format!("{registers:?}");
+        assert_eq!(debug_output, "{0: 42, 2: 10, 1000000000: 7}");
+    }
+
+    #[test]
+    fn test_inspection() {
+        let mut registers: Registers<i64> = Registers::new(StorageKind::Dense);
+        assert_eq!(registers.highest_index(), None);
+
+        registers.set(9, 3);
+        registers.set(4, 1);
+        registers.set(12, 0);
+        assert_eq!(registers.highest_index(), Some(RegisterId(12)));
+        assert_eq!(
+            registers.iter().collect::<Vec<_>>(),
+            [(RegisterId(4), &1), (RegisterId(9), &3)]
+        );
+
+        let mut other = registers.clone().with_storage(StorageKind::Sparse);
+        assert_eq!(other.highest_index(), Some(RegisterId(12)));
+        assert!(registers.diff(&other).is_empty());
+        other.set(4, 2);
+        other.set(20, 5);
+        assert_eq!(
+            registers.diff(&other),
+            [
+                RegisterDiff {
+                    index: RegisterId(4),
+                    left: 1,
+                    right: 2
+                },
+                RegisterDiff {
+                    index: RegisterId(20),
+                    left: 0,
+                    right: 5
+                },
+            ]
+        );
+
+        registers.clear();
+        assert_eq!(registers.storage_kind(), StorageKind::Dense);
+        assert_eq!(registers.highest_index(), None);
+        assert_eq!(registers.display().to_string(), "");
+    }
+
+    #[test]
+    fn test_display() {
+        let registers: Registers<i64> = [1, 2, 2, 0, 2, 255].into();
+        assert_eq!(
+            registers.display().to_string(),
+            "R0 = 1\nR1..=R2 = 2\nR4 = 2\nR5 = 255\n"
+        );
+        assert_eq!(
+            registers.display().with_hex(true).to_string(),
+            "R0 = 0x1\nR1..=R2 = 0x2\nR4 = 0x2\nR5 = 0xff\n"
+        );
+
+        let registers: Registers<f64> = [0.5, 2.0].into();
+        assert_eq!(
+            registers.display().with_hex(true).to_string(),
+            "R0 = 0.5\nR1 = 0x2\n"
+        );
+
+        let registers: Registers<u64> = [u64::MAX].into();
+        assert_eq!(
+            registers.display().with_hex(true).to_string(),
+            "R0 = 0xffffffffffffffff\n"
+        );
+        let registers: Registers<i8> = [-128].into();
+        assert_eq!(
+            registers.display().with_hex(true).to_string(),
+            "R0 = -0x80\n"
+        );
+        let registers: Registers<BigInt> = [BigInt::parse("18446744073709551616").unwrap()].into();
+        assert_eq!(
+            registers.display().with_hex(true).to_string(),
+            "R0 = 0x10000000000000000\n"
+        );
     }
 }
@@ -59,86 +59,377 @@
 //! It also demonstrates the use of `Value` and `RegisterValue` for specifying operands in the
 //! assembly language code.
 
+use crate::extension::{InstructionId, Operand};
+use crate::program::HostCallId;
 use crate::program::LabelId;
+use crate::program::StringId;
 
 /// Represents a statement in the program, along with its line number from the source code.
 /// Statements are the basic building blocks of a program and define the operations to be performed.
+///
+/// `T` is the type of the machine word, see [`Value`].
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub struct Stmt {
+pub struct Stmt<T = i64> {
     /// The operation to be performed by the statement.
-    pub op: Op,
+    pub op: Op<T>,
     /// The line number from the source code.
     pub line: usize,
 }
 
-impl AsRef<Self> for Stmt {
+impl<T> AsRef<Self> for Stmt<T> {
     fn as_ref(&self) -> &Self {
         self
     }
 }
 
-impl AsMut<Self> for Stmt {
+impl<T> AsMut<Self> for Stmt<T> {
     fn as_mut(&mut self) -> &mut Self {
         self
     }
 }
 
-impl PartialOrd for Stmt {
+impl<T: Eq> PartialOrd for Stmt<T> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.line.cmp(&other.line))
+        Some(self.cmp(other))
     }
 }
 
-impl Ord for Stmt {
+impl<T: Eq> Ord for Stmt<T> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.line.cmp(&other.line)
     }
 }
 
-impl Stmt {
+impl<T> Stmt<T> {
     /// Creates a new statement with the specified operation and line number.
     #[must_use]
-    pub fn new(op: Op, line: usize) -> Self {
+    pub fn new(op: Op<T>, line: usize) -> Self {
         Self { op, line }
     }
 }
 
 /// Represents an operation that can be performed by the program.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum Op {
+pub enum Op<T = i64> {
     /// Loads value into register `0`
-    Load(Value),
+    Load(Value<T>),
     /// Stores value from register `0` into register
     Store(RegisterValue),
     /// Adds value to register `0`
-    Add(Value),
+    Add(Value<T>),
     /// Subtracts value from register `0`
-    Sub(Value),
+    Sub(Value<T>),
     /// Multiplies value with register `0`
-    Mult(Value),
+    Mult(Value<T>),
     /// Divides register `0` by value
-    Div(Value),
+    Div(Value<T>),
     /// Jumps to label
     Jump(LabelId),
     /// Jumps to label if register `0` is equal to `0`
     JumpIfZero(LabelId),
     /// Jumps to label if register `0` is greater than `0`
     JumpGreatherZero(LabelId),
+    /// Increments value in register by `1`
+    Inc(RegisterValue),
+    /// Decrements value in register by `1`
+    Dec(RegisterValue),
+    /// Negates register `0`
+    Neg,
+    /// Replaces register `0` with its absolute value
+    Abs,
+    /// Rounds register `0` down to an integer
+    Floor,
+    /// Rounds register `0` towards zero to an integer
+    Trunc,
     /// Inputs value from `reader`
     Input(RegisterValue),
     /// Outputs value to `writer`
-    Output(Value),
+    Output(Value<T>),
+    /// Inputs a single character from `reader` as its code
+    InputChar(RegisterValue),
+    /// Outputs value as a single character to `writer`
+    OutputChar(Value<T>),
+    /// Outputs a string literal stored in the [`Program`] to `writer`
+    ///
+    /// [`Program`]: crate::program::Program
+    Print(StringId),
+    /// Loads a pseudo-random number in range from `0` up to value (exclusive) into register `0`
+    Rand(Value<T>),
+    /// Loads the number of instructions executed so far into register `0`
+    Clock,
+    /// Stops the machine with an error if the comparison of two values does not hold
+    Assert(Value<T>, Comparison, Value<T>),
+    /// Calls a host function registered on the [`Ram`]
+    ///
+    /// [`Ram`]: crate::ram::Ram
+    Sys(HostCallId),
     /// Halts program
     Halt,
+    /// Executes a user-defined instruction registered in [`Extensions`]
+    ///
+    /// [`Extensions`]: crate::extension::Extensions
+    Custom(InstructionId, Operand<T>),
+}
+
+impl<T> Op<T> {
+    /// Returns the kind of the operation, without its operand.
+    #[must_use]
+    pub fn kind(&self) -> OpKind {
+        match self {
+            Self::Load(_) => OpKind::Load,
+            Self::Store(_) => OpKind::Store,
+            Self::Add(_) => OpKind::Add,
+            Self::Sub(_) => OpKind::Sub,
+            Self::Mult(_) => OpKind::Mult,
+            Self::Div(_) => OpKind::Div,
+            Self::Jump(_) => OpKind::Jump,
+            Self::JumpIfZero(_) => OpKind::JumpIfZero,
+            Self::JumpGreatherZero(_) => OpKind::JumpGreatherZero,
+            Self::Inc(_) => OpKind::Inc,
+            Self::Dec(_) => OpKind::Dec,
+            Self::Neg => OpKind::Neg,
+            Self::Abs => OpKind::Abs,
+            Self::Floor => OpKind::Floor,
+            Self::Trunc => OpKind::Trunc,
+            Self::Input(_) => OpKind::Input,
+            Self::Output(_) => OpKind::Output,
+            Self::InputChar(_) => OpKind::InputChar,
+            Self::OutputChar(_) => OpKind::OutputChar,
+            Self::Print(_) => OpKind::Print,
+            Self::Rand(_) => OpKind::Rand,
+            Self::Clock => OpKind::Clock,
+            Self::Assert(..) => OpKind::Assert,
+            Self::Sys(_) => OpKind::Sys,
+            Self::Halt => OpKind::Halt,
+            Self::Custom(..) => OpKind::Custom,
+        }
+    }
+
+    /// Returns the addressing mode of the operand, if the operation has one.
+    #[must_use]
+    pub fn addressing_mode(&self) -> Option<AddressingMode> {
+        match self {
+            Self::Load(value)
+            | Self::Add(value)
+            | Self::Sub(value)
+            | Self::Mult(value)
+            | Self::Div(value)
+            | Self::Output(value)
+            | Self::OutputChar(value)
+            | Self::Rand(value)
+            | Self::Assert(value, ..)
+            | Self::Custom(_, Operand::Value(value)) => Some(value.addressing_mode()),
+            Self::Store(register)
+            | Self::Inc(register)
+            | Self::Dec(register)
+            | Self::Input(register)
+            | Self::InputChar(register)
+            | Self::Custom(_, Operand::Register(register)) => Some(register.addressing_mode()),
+            _ => None,
+        }
+    }
+}
+
+/// Represents a kind of operation, see [`Op`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum OpKind {
+    /// See [`Op::Load`]
+    Load,
+    /// See [`Op::Store`]
+    Store,
+    /// See [`Op::Add`]
+    Add,
+    /// See [`Op::Sub`]
+    Sub,
+    /// See [`Op::Mult`]
+    Mult,
+    /// See [`Op::Div`]
+    Div,
+    /// See [`Op::Jump`]
+    Jump,
+    /// See [`Op::JumpIfZero`]
+    JumpIfZero,
+    /// See [`Op::JumpGreatherZero`]
+    JumpGreatherZero,
+    /// See [`Op::Inc`]
+    Inc,
+    /// See [`Op::Dec`]
+    Dec,
+    /// See [`Op::Neg`]
+    Neg,
+    /// See [`Op::Abs`]
+    Abs,
+    /// See [`Op::Floor`]
+    Floor,
+    /// See [`Op::Trunc`]
+    Trunc,
+    /// See [`Op::Input`]
+    Input,
+    /// See [`Op::Output`]
+    Output,
+    /// See [`Op::InputChar`]
+    InputChar,
+    /// See [`Op::OutputChar`]
+    OutputChar,
+    /// See [`Op::Print`]
+    Print,
+    /// See [`Op::Rand`]
+    Rand,
+    /// See [`Op::Clock`]
+    Clock,
+    /// See [`Op::Assert`]
+    Assert,
+    /// See [`Op::Sys`]
+    Sys,
+    /// See [`Op::Halt`]
+    Halt,
+    /// See [`Op::Custom`]
+    Custom,
+}
+
+impl OpKind {
+    /// All kinds of operations.
+    pub const ALL: [Self; 26] = [
+        Self::Load,
+        Self::Store,
+        Self::Add,
+        Self::Sub,
+        Self::Mult,
+        Self::Div,
+        Self::Jump,
+        Self::JumpIfZero,
+        Self::JumpGreatherZero,
+        Self::Inc,
+        Self::Dec,
+        Self::Neg,
+        Self::Abs,
+        Self::Floor,
+        Self::Trunc,
+        Self::Input,
+        Self::Output,
+        Self::InputChar,
+        Self::OutputChar,
+        Self::Print,
+        Self::Rand,
+        Self::Clock,
+        Self::Assert,
+        Self::Sys,
+        Self::Halt,
+        Self::Custom,
+    ];
+}
+
+impl std::fmt::Display for OpKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Load => write!(f, "LOAD"),
+            Self::Store => write!(f, "STORE"),
+            Self::Add => write!(f, "ADD"),
+            Self::Sub => write!(f, "SUB"),
+            Self::Mult => write!(f, "MULT"),
+            Self::Div => write!(f, "DIV"),
+            Self::Jump => write!(f, "JUMP"),
+            Self::JumpIfZero => write!(f, "JZERO"),
+            Self::JumpGreatherZero => write!(f, "JGTZ"),
+            Self::Inc => write!(f, "INC"),
+            Self::Dec => write!(f, "DEC"),
+            Self::Neg => write!(f, "NEG"),
+            Self::Abs => write!(f, "ABS"),
+            Self::Floor => write!(f, "FLOOR"),
+            Self::Trunc => write!(f, "TRUNC"),
+            Self::Input => write!(f, "READ"),
+            Self::Output => write!(f, "WRITE"),
+            Self::InputChar => write!(f, "READC"),
+            Self::OutputChar => write!(f, "WRITEC"),
+            Self::Print => write!(f, "PRINT"),
+            Self::Rand => write!(f, "RAND"),
+            Self::Clock => write!(f, "CLOCK"),
+            Self::Assert => write!(f, "ASSERT"),
+            Self::Sys => write!(f, "SYS"),
+            Self::Halt => write!(f, "HALT"),
+            Self::Custom => write!(f, "Custom Instruction"),
+        }
+    }
+}
+
+/// Represents an addressing mode of an operand.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AddressingMode {
+    /// A pure value, e.g. `LOAD =5`.
+    Immediate,
+    /// A register, e.g. `LOAD 5`.
+    Direct,
+    /// A register whose number is stored in another register, e.g. `LOAD *5`.
+    Indirect,
+}
+
+impl AddressingMode {
+    /// All addressing modes.
+    pub const ALL: [Self; 3] = [Self::Immediate, Self::Direct, Self::Indirect];
+}
+
+impl std::fmt::Display for AddressingMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Immediate => write!(f, "Immediate Addressing"),
+            Self::Direct => write!(f, "Direct Addressing"),
+            Self::Indirect => write!(f, "Indirect Addressing"),
+        }
+    }
+}
+
+/// Represents a comparison of two values, e.g. in `ASSERT 1 > =0`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Comparison {
+    /// `==`
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `<`
+    Less,
+    /// `<=`
+    LessOrEqual,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    /// Returns `true` if the comparison holds for the given values.
+    #[must_use]
+    pub fn holds<T: PartialOrd>(&self, lhs: &T, rhs: &T) -> bool {
+        match self {
+            Self::Equal => lhs == rhs,
+            Self::NotEqual => lhs != rhs,
+            Self::Less => lhs < rhs,
+            Self::LessOrEqual => lhs <= rhs,
+            Self::Greater => lhs > rhs,
+            Self::GreaterOrEqual => lhs >= rhs,
+        }
+    }
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Equal => write!(f, "=="),
+            Self::NotEqual => write!(f, "!="),
+            Self::Less => write!(f, "<"),
+            Self::LessOrEqual => write!(f, "<="),
+            Self::Greater => write!(f, ">"),
+            Self::GreaterOrEqual => write!(f, ">="),
+        }
+    }
 }
 
-impl AsRef<Self> for Op {
+impl<T> AsRef<Self> for Op<T> {
     fn as_ref(&self) -> &Self {
         self
     }
 }
 
-impl AsMut<Self> for Op {
+impl<T> AsMut<Self> for Op<T> {
     fn as_mut(&mut self) -> &mut Self {
         self
     }
@@ -147,14 +438,19 @@ impl AsMut<Self> for Op {
 /// Represents a value that can be passed to a statement.
 /// The value can be a pure numeric value or a value stored in a register.
 ///
+/// `T` is the type of the machine word, so a pure value is exactly what ends
+/// up in a register of a [`Ram<T>`].
+///
 /// Examples:
 /// - `LOAD =5`: Loads the pure numeric value `5` into register 0.
 /// - `LOAD 5`: Loads the value stored in register 5 into register 0.
+///
+/// [`Ram<T>`]: crate::ram::Ram
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub enum Value {
+pub enum Value<T = i64> {
     /// Represents a pure numeric value, indicated by an equal sign (`=`) before the number.
     /// For example, `LOAD =5` loads the pure numeric value `5` into register 0.
-    Pure(isize),
+    Pure(T),
     /// Represents the value stored in a specific register.
     // /// For example, `LOAD 5` loads the value stored in register 5 into register 0.
     Register(RegisterValue),
@@ -175,13 +471,35 @@ pub enum RegisterValue {
     Indirect(usize),
 }
 
-impl AsRef<Self> for Value {
+impl<T> Value<T> {
+    /// Returns the addressing mode of the value.
+    #[must_use]
+    pub fn addressing_mode(&self) -> AddressingMode {
+        match self {
+            Self::Pure(_) => AddressingMode::Immediate,
+            Self::Register(register) => register.addressing_mode(),
+        }
+    }
+}
+
+impl RegisterValue {
+    /// Returns the addressing mode of the register.
+    #[must_use]
+    pub fn addressing_mode(&self) -> AddressingMode {
+        match self {
+            Self::Direct(_) => AddressingMode::Direct,
+            Self::Indirect(_) => AddressingMode::Indirect,
+        }
+    }
+}
+
+impl<T> AsRef<Self> for Value<T> {
     fn as_ref(&self) -> &Self {
         self
     }
 }
 
-impl AsMut<Self> for Value {
+impl<T> AsMut<Self> for Value<T> {
     fn as_mut(&mut self) -> &mut Self {
         self
     }
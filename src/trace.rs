@@ -0,0 +1,142 @@
+//! The `trace` module records the register accesses a [`Ram`] performs, for
+//! example to feed them into the [`cache`] simulator.
+//!
+//! A [`MemoryTrace`] is a shared handle: clone it, pass one clone to
+//! [`Ram::with_memory_trace`] and read the accesses from the other one while
+//! or after the program runs.
+//!
+//! Every access to the accumulator `R0` is recorded, as is every hop of an
+//! indirect operand: `LOAD *3` reads `R3` and then the register it points to.
+//! In RASP mode, the two words of every instruction are recorded as fetches.
+//!
+//! # Examples
+//!
+//! ```
+//! use ramemu::program::Program;
+//! use ramemu::ram::Ram;
+//! use ramemu::registers::RegisterId;
+//! use ramemu::trace::{Access, AccessKind, MemoryTrace};
+//! use std::io::BufReader;
+//! use std::io::BufWriter;
+//!
+//! let program = Program::from_source("load =5\nstore 3\nload *3\nhalt").unwrap();
+//! let reader = BufReader::new(std::io::empty());
+//! let writer = BufWriter::new(std::io::sink());
+//! let trace = MemoryTrace::new();
+//! let mut ram = Ram::new(program, Box::new(reader), Box::new(writer))
+//!     .with_memory_trace(trace.clone());
+//!
+//! ram.run().unwrap();
+//! assert_eq!(
+//!     trace.take()[3..],
+//!     [
+//!         Access::new(AccessKind::Read, RegisterId(3), 3),
+//!         Access::new(AccessKind::Read, RegisterId(5), 3),
+//!         Access::new(AccessKind::Write, RegisterId(0), 3),
+//!     ]
+//! );
+//! ```
+//!
+//! [`Ram`]: crate::ram::Ram
+//! [`Ram::with_memory_trace`]: crate::ram::Ram::with_memory_trace
+//! [`cache`]: crate::cache
+
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use crate::registers::RegisterId;
+
+/// The kind of a register access.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AccessKind {
+    /// An operand or the accumulator is read.
+    Read,
+    /// A register or the accumulator is written.
+    Write,
+    /// A word of an instruction is read in RASP mode.
+    Fetch,
+}
+
+/// One register access of a [`MemoryTrace`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Access {
+    /// The kind of the access.
+    pub kind: AccessKind,
+    /// The accessed register.
+    pub register: RegisterId,
+    /// The source line of the statement that performed the access.
+    pub line: usize,
+}
+
+impl Access {
+    /// Creates a new [`Access`].
+    #[inline]
+    #[must_use]
+    pub fn new(kind: AccessKind, register: RegisterId, line: usize) -> Self {
+        Self {
+            kind,
+            register,
+            line,
+        }
+    }
+}
+
+/// A shared, growing record of register accesses.
+#[derive(Default, Debug, Clone)]
+pub struct MemoryTrace {
+    accesses: Rc<RefCell<Vec<Access>>>,
+}
+
+impl MemoryTrace {
+    /// Creates a new empty [`MemoryTrace`].
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an access to the trace.
+    #[inline]
+    pub fn record(&self, access: Access) {
+        self.accesses.borrow_mut().push(access);
+    }
+
+    /// Borrows the accesses recorded so far, in order.
+    ///
+    /// # Panics
+    /// Recording an access while the borrow is alive panics, so drop it
+    /// before the [`Ram`] runs again.
+    ///
+    /// [`Ram`]: crate::ram::Ram
+    #[inline]
+    #[must_use]
+    pub fn accesses(&self) -> Ref<'_, [Access]> {
+        Ref::map(self.accesses.borrow(), Vec::as_slice)
+    }
+
+    /// Removes and returns the accesses recorded so far, in order.
+    #[inline]
+    #[must_use]
+    pub fn take(&self) -> Vec<Access> {
+        self.accesses.take()
+    }
+
+    /// Returns the number of recorded accesses.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.accesses.borrow().len()
+    }
+
+    /// Returns `true` if no access has been recorded.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.accesses.borrow().is_empty()
+    }
+
+    /// Removes every recorded access.
+    pub fn clear(&self) {
+        self.accesses.borrow_mut().clear();
+    }
+}
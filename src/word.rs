@@ -0,0 +1,698 @@
+//! The `word` module defines the [`Word`] trait for values a [`Ram`] can hold
+//! in its registers.
+//!
+//! The crate implements it for the primitive integer types, with `i64` being
+//! the default integer RAM, and for `f64`, the Real RAM used in computational
+//! geometry, where `DIV` is real division and results that are not finite stop
+//! the machine. Integer arithmetic that leaves the range of the type is an
+//! [`Overflow`], unless the [`Ram`] wraps or saturates it, see [`OverflowMode`].
+//!
+//! # Examples
+//!
+//! ```
+//! use ramemu::program::Program;
+//! use ramemu::ram::Ram;
+//! use std::io::BufReader;
+//! use std::io::BufWriter;
+//!
+//! let program = Program::from_source("load =7\ndiv =2\nstore 1\nfloor\nhalt").unwrap();
+//! let reader = BufReader::new(std::io::empty());
+//! let writer = BufWriter::new(std::io::sink());
+//! let mut ram: Ram<f64> = Ram::from_program(program, Box::new(reader), Box::new(writer));
+//!
+//! ram.run().unwrap();
+//! assert_eq!(ram.get_registers().get(1), 3.5);
+//! assert_eq!(ram.get_registers().get(0), 3.0);
+//! ```
+//!
+//! [`Ram`]: crate::ram::Ram
+//! [`Overflow`]: crate::errors::InterpretErrorKind::Overflow
+
+use std::fmt::Debug;
+use std::fmt::Display;
+
+use crate::errors::InterpretErrorKind;
+
+/// A value that can be stored in the registers of a [`Ram`].
+///
+/// Arithmetic returns an [`InterpretErrorKind`] instead of a value when the
+/// result can not be represented. [`Default`] must be zero.
+///
+/// [`Ram`]: crate::ram::Ram
+pub trait Word: Clone + Default + Debug + Display + PartialEq + PartialOrd + 'static {
+    /// Converts an integer, e.g. a pure operand or a character code.
+    ///
+    /// If the integer can not be represented exactly, returns `None`.
+    fn from_i64(value: i64) -> Option<Self>;
+
+    /// Converts the value to an integer, e.g. for character output.
+    ///
+    /// If the value is not an integer or does not fit into `i64`, returns `None`.
+    fn to_i64(&self) -> Option<i64>;
+
+    /// Parses a numeric literal or a value read by `READ`.
+    ///
+    /// If the text is not a valid value, returns `None`.
+    fn parse(text: &str) -> Option<Self>;
+
+    /// Adds two values.
+    /// # Errors
+    /// Returns an [`InterpretErrorKind`] if the result can not be represented.
+    fn add(&self, rhs: &Self) -> Result<Self, InterpretErrorKind>;
+
+    /// Subtracts two values.
+    /// # Errors
+    /// Returns an [`InterpretErrorKind`] if the result can not be represented.
+    fn sub(&self, rhs: &Self) -> Result<Self, InterpretErrorKind>;
+
+    /// Multiplies two values.
+    /// # Errors
+    /// Returns an [`InterpretErrorKind`] if the result can not be represented.
+    fn mul(&self, rhs: &Self) -> Result<Self, InterpretErrorKind>;
+
+    /// Divides two values.
+    /// # Errors
+    /// Returns [`InterpretErrorKind::DivisionByZero`] if `rhs` is zero, or
+    /// another [`InterpretErrorKind`] if the result can not be represented.
+    fn div(&self, rhs: &Self) -> Result<Self, InterpretErrorKind>;
+
+    /// Negates the value.
+    /// # Errors
+    /// Returns an [`InterpretErrorKind`] if the result can not be represented.
+    fn neg(&self) -> Result<Self, InterpretErrorKind>;
+
+    /// Returns the absolute value.
+    /// # Errors
+    /// Returns an [`InterpretErrorKind`] if the result can not be represented.
+    fn abs(&self) -> Result<Self, InterpretErrorKind>;
+
+    /// Rounds the value down to an integer.
+    #[must_use]
+    fn floor(&self) -> Self;
+
+    /// Rounds the value towards zero to an integer.
+    #[must_use]
+    fn trunc(&self) -> Self;
+
+    /// Adds two values, resolving an overflow according to `mode`.
+    ///
+    /// Words without a fixed width only support [`OverflowMode::Checked`]
+    /// and ignore `mode`, as do the other `*_with` methods.
+    /// # Errors
+    /// Returns an [`InterpretErrorKind`] if the result can not be represented.
+    #[inline]
+    fn add_with(&self, rhs: &Self, _mode: OverflowMode) -> Result<Self, InterpretErrorKind> {
+        self.add(rhs)
+    }
+
+    /// Subtracts two values, resolving an overflow according to `mode`.
+    /// # Errors
+    /// Returns an [`InterpretErrorKind`] if the result can not be represented.
+    #[inline]
+    fn sub_with(&self, rhs: &Self, _mode: OverflowMode) -> Result<Self, InterpretErrorKind> {
+        self.sub(rhs)
+    }
+
+    /// Multiplies two values, resolving an overflow according to `mode`.
+    /// # Errors
+    /// Returns an [`InterpretErrorKind`] if the result can not be represented.
+    #[inline]
+    fn mul_with(&self, rhs: &Self, _mode: OverflowMode) -> Result<Self, InterpretErrorKind> {
+        self.mul(rhs)
+    }
+
+    /// Divides two values, rounding the quotient according to `division` and
+    /// resolving an overflow according to `overflow`.
+    /// # Errors
+    /// Returns [`InterpretErrorKind::DivisionByZero`] if `rhs` is zero, in
+    /// every mode, or another [`InterpretErrorKind`] if the result can not be
+    /// represented.
+    #[inline]
+    fn div_with(
+        &self,
+        rhs: &Self,
+        division: DivisionMode,
+        _overflow: OverflowMode,
+    ) -> Result<Self, InterpretErrorKind> {
+        div_rem(self, rhs, division).map(|(quotient, _)| quotient)
+    }
+
+    /// Returns the remainder of dividing two values, matching the quotient of
+    /// [`Word::div_with`] in the same `division` mode.
+    ///
+    /// The remainder always fits, so there is no overflow mode.
+    /// # Errors
+    /// Returns [`InterpretErrorKind::DivisionByZero`] if `rhs` is zero.
+    #[inline]
+    fn rem_with(&self, rhs: &Self, division: DivisionMode) -> Result<Self, InterpretErrorKind> {
+        div_rem(self, rhs, division).map(|(_, remainder)| remainder)
+    }
+
+    /// Negates the value, resolving an overflow according to `mode`.
+    /// # Errors
+    /// Returns an [`InterpretErrorKind`] if the result can not be represented.
+    #[inline]
+    fn neg_with(&self, _mode: OverflowMode) -> Result<Self, InterpretErrorKind> {
+        self.neg()
+    }
+
+    /// Returns the absolute value, resolving an overflow according to `mode`.
+    /// # Errors
+    /// Returns an [`InterpretErrorKind`] if the result can not be represented.
+    #[inline]
+    fn abs_with(&self, _mode: OverflowMode) -> Result<Self, InterpretErrorKind> {
+        self.abs()
+    }
+
+    /// Converts a counter such as the step clock, resolving a value that does
+    /// not fit according to `mode`.
+    /// # Errors
+    /// Returns [`InterpretErrorKind::Overflow`] if the value can not be
+    /// represented.
+    #[inline]
+    fn from_u64_with(value: u64, _mode: OverflowMode) -> Result<Self, InterpretErrorKind> {
+        i64::try_from(value)
+            .ok()
+            .and_then(Self::from_i64)
+            .ok_or(InterpretErrorKind::Overflow)
+    }
+
+    /// Formats the value in hexadecimal, e.g. `0x1f` or `-0x1f`.
+    ///
+    /// Returns `None` if the value is not an integer. The default covers
+    /// values that fit into `i64`.
+    #[inline]
+    fn to_hex(&self) -> Option<String> {
+        self.to_i64().map(|value| {
+            if value < 0 {
+                format!("-{:#x}", value.unsigned_abs())
+            } else {
+                format!("{value:#x}")
+            }
+        })
+    }
+
+    /// Converts the value to a register index.
+    ///
+    /// If the value is not a non-negative integer that fits into `usize`, returns `None`.
+    #[inline]
+    fn to_index(&self) -> Option<usize> {
+        self.to_i64().and_then(|value| usize::try_from(value).ok())
+    }
+
+    /// Returns `true` if the value is zero.
+    #[inline]
+    fn is_zero(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns `true` if the value is greater than zero.
+    #[inline]
+    fn is_positive(&self) -> bool {
+        *self > Self::default()
+    }
+}
+
+/// Selects how fixed-width words handle results that do not fit.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum OverflowMode {
+    /// Stops the machine with [`InterpretErrorKind::Overflow`].
+    #[default]
+    Checked,
+    /// Wraps around at the boundary of the type, e.g. `MAX + 1 == MIN`.
+    Wrapping,
+    /// Clamps the result to the nearest bound, e.g. `MAX + 1 == MAX`.
+    Saturating,
+}
+
+impl OverflowMode {
+    /// Turns the result of a checked operation into the result in this mode.
+    #[inline]
+    fn resolve<T>(
+        self,
+        checked: Option<T>,
+        wrapped: impl FnOnce() -> T,
+        saturated: impl FnOnce() -> T,
+    ) -> Result<T, InterpretErrorKind> {
+        match (checked, self) {
+            (Some(value), _) => Ok(value),
+            (None, Self::Checked) => Err(InterpretErrorKind::Overflow),
+            (None, Self::Wrapping) => Ok(wrapped()),
+            (None, Self::Saturating) => Ok(saturated()),
+        }
+    }
+}
+
+/// Selects how integer division rounds a quotient that is not exact.
+///
+/// The remainder `r` of `a / b` always satisfies `a == q * b + r`.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DivisionMode {
+    /// Rounds towards zero, e.g. `-7 / 2 == -3` with remainder `-1`.
+    #[default]
+    Truncating,
+    /// Rounds towards negative infinity, e.g. `-7 / 2 == -4` with remainder `1`.
+    /// The remainder has the sign of the divisor.
+    Floor,
+    /// Rounds so that the remainder is never negative, e.g. `-7 / -2 == 4`
+    /// with remainder `1`.
+    Euclidean,
+}
+
+/// Divides with [`Word`] operations only, for words without native support
+/// for the division modes. [`Word::div`] must truncate.
+fn div_rem<T: Word>(
+    lhs: &T,
+    rhs: &T,
+    division: DivisionMode,
+) -> Result<(T, T), InterpretErrorKind> {
+    let quotient = lhs.div(rhs)?;
+    let remainder = lhs.sub(&quotient.mul(rhs)?)?;
+    let negative = |value: &T| !value.is_zero() && !value.is_positive();
+    let adjust = match division {
+        DivisionMode::Truncating => false,
+        DivisionMode::Floor => !remainder.is_zero() && negative(&remainder) != negative(rhs),
+        DivisionMode::Euclidean => negative(&remainder),
+    };
+    if !adjust {
+        return Ok((quotient, remainder));
+    }
+
+    let one = T::from_i64(1).ok_or(InterpretErrorKind::Overflow)?;
+    if negative(rhs) && division == DivisionMode::Euclidean {
+        Ok((quotient.add(&one)?, remainder.sub(rhs)?))
+    } else {
+        Ok((quotient.sub(&one)?, remainder.add(rhs)?))
+    }
+}
+
+/// Implements [`Word`] for a primitive integer type with checked arithmetic.
+///
+/// `abs` is the identity for unsigned types and `neg` only succeeds for zero.
+macro_rules! impl_word_for_integer {
+    ($($ty:ty),*) => {$(
+        impl Word for $ty {
+            #[inline]
+            fn from_i64(value: i64) -> Option<Self> {
+                Self::try_from(value).ok()
+            }
+
+            #[inline]
+            fn to_i64(&self) -> Option<i64> {
+                i64::try_from(*self).ok()
+            }
+
+            #[inline]
+            fn parse(text: &str) -> Option<Self> {
+                text.parse().ok()
+            }
+
+            #[inline]
+            fn add(&self, rhs: &Self) -> Result<Self, InterpretErrorKind> {
+                self.checked_add(*rhs).ok_or(InterpretErrorKind::Overflow)
+            }
+
+            #[inline]
+            fn sub(&self, rhs: &Self) -> Result<Self, InterpretErrorKind> {
+                self.checked_sub(*rhs).ok_or(InterpretErrorKind::Overflow)
+            }
+
+            #[inline]
+            fn mul(&self, rhs: &Self) -> Result<Self, InterpretErrorKind> {
+                self.checked_mul(*rhs).ok_or(InterpretErrorKind::Overflow)
+            }
+
+            #[inline]
+            fn div(&self, rhs: &Self) -> Result<Self, InterpretErrorKind> {
+                if *rhs == 0 {
+                    return Err(InterpretErrorKind::DivisionByZero);
+                }
+                self.checked_div(*rhs).ok_or(InterpretErrorKind::Overflow)
+            }
+
+            #[inline]
+            fn neg(&self) -> Result<Self, InterpretErrorKind> {
+                self.checked_neg().ok_or(InterpretErrorKind::Overflow)
+            }
+
+            #[inline]
+            #[allow(unused_comparisons)]
+            fn abs(&self) -> Result<Self, InterpretErrorKind> {
+                if *self >= 0 {
+                    Ok(*self)
+                } else {
+                    self.checked_neg().ok_or(InterpretErrorKind::Overflow)
+                }
+            }
+
+            #[inline]
+            fn floor(&self) -> Self {
+                *self
+            }
+
+            #[inline]
+            fn trunc(&self) -> Self {
+                *self
+            }
+
+            #[inline]
+            #[allow(unused_comparisons)]
+            fn to_hex(&self) -> Option<String> {
+                if *self < 0 {
+                    Some(format!("-{:#x}", self.abs_diff(0)))
+                } else {
+                    Some(format!("{self:#x}"))
+                }
+            }
+
+            #[inline]
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            fn from_u64_with(value: u64, mode: OverflowMode) -> Result<Self, InterpretErrorKind> {
+                mode.resolve(Self::try_from(value).ok(), || value as Self, || Self::MAX)
+            }
+
+            #[inline]
+            fn add_with(&self, rhs: &Self, mode: OverflowMode) -> Result<Self, InterpretErrorKind> {
+                mode.resolve(
+                    self.checked_add(*rhs),
+                    || self.wrapping_add(*rhs),
+                    || self.saturating_add(*rhs),
+                )
+            }
+
+            #[inline]
+            fn sub_with(&self, rhs: &Self, mode: OverflowMode) -> Result<Self, InterpretErrorKind> {
+                mode.resolve(
+                    self.checked_sub(*rhs),
+                    || self.wrapping_sub(*rhs),
+                    || self.saturating_sub(*rhs),
+                )
+            }
+
+            #[inline]
+            fn mul_with(&self, rhs: &Self, mode: OverflowMode) -> Result<Self, InterpretErrorKind> {
+                mode.resolve(
+                    self.checked_mul(*rhs),
+                    || self.wrapping_mul(*rhs),
+                    || self.saturating_mul(*rhs),
+                )
+            }
+
+            #[inline]
+            #[allow(unused_comparisons)]
+            fn div_with(
+                &self,
+                rhs: &Self,
+                division: DivisionMode,
+                overflow: OverflowMode,
+            ) -> Result<Self, InterpretErrorKind> {
+                if *rhs == 0 {
+                    return Err(InterpretErrorKind::DivisionByZero);
+                }
+                let quotient = match division {
+                    DivisionMode::Truncating => self.checked_div(*rhs),
+                    DivisionMode::Floor => self.checked_div(*rhs).map(|quotient| {
+                        let remainder = self % rhs;
+                        if remainder != 0 && (remainder < 0) != (*rhs < 0) {
+                            quotient - 1
+                        } else {
+                            quotient
+                        }
+                    }),
+                    DivisionMode::Euclidean => self.checked_div_euclid(*rhs),
+                };
+                // Only `MIN / -1` overflows.
+                overflow.resolve(quotient, || Self::MIN, || Self::MAX)
+            }
+
+            #[inline]
+            #[allow(unused_comparisons)]
+            fn rem_with(
+                &self,
+                rhs: &Self,
+                division: DivisionMode,
+            ) -> Result<Self, InterpretErrorKind> {
+                if *rhs == 0 {
+                    return Err(InterpretErrorKind::DivisionByZero);
+                }
+                // Only `MIN % -1` fails, and its remainder is zero.
+                Ok(match division {
+                    DivisionMode::Truncating => self.checked_rem(*rhs).unwrap_or(0),
+                    DivisionMode::Floor => match self.checked_rem(*rhs).unwrap_or(0) {
+                        r if r != 0 && (r < 0) != (*rhs < 0) => r + rhs,
+                        r => r,
+                    },
+                    DivisionMode::Euclidean => self.checked_rem_euclid(*rhs).unwrap_or(0),
+                })
+            }
+
+            #[inline]
+            fn neg_with(&self, mode: OverflowMode) -> Result<Self, InterpretErrorKind> {
+                let bound = if *self > 0 { Self::MIN } else { Self::MAX };
+                mode.resolve(self.checked_neg(), || self.wrapping_neg(), || bound)
+            }
+
+            #[inline]
+            fn abs_with(&self, mode: OverflowMode) -> Result<Self, InterpretErrorKind> {
+                // Only `MIN` of a signed type overflows, and it wraps to itself.
+                mode.resolve(Word::abs(self).ok(), || *self, || Self::MAX)
+            }
+        }
+    )*};
+}
+
+impl_word_for_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl Word for f64 {
+    #[inline]
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    fn from_i64(value: i64) -> Option<Self> {
+        let real = value as f64;
+        // `i64::MAX` rounds up to 2^63, which saturates back to `i64::MAX`.
+        (real as i64 == value && real < 9_223_372_036_854_775_808.0).then_some(real)
+    }
+
+    #[inline]
+    #[allow(clippy::cast_possible_truncation, clippy::float_cmp)]
+    fn to_i64(&self) -> Option<i64> {
+        let integer = *self as i64;
+        (integer as f64 == *self && *self < 9_223_372_036_854_775_808.0).then_some(integer)
+    }
+
+    #[inline]
+    fn parse(text: &str) -> Option<Self> {
+        text.parse().ok().filter(|value: &Self| value.is_finite())
+    }
+
+    #[inline]
+    fn add(&self, rhs: &Self) -> Result<Self, InterpretErrorKind> {
+        finite(self + rhs)
+    }
+
+    #[inline]
+    fn sub(&self, rhs: &Self) -> Result<Self, InterpretErrorKind> {
+        finite(self - rhs)
+    }
+
+    #[inline]
+    fn mul(&self, rhs: &Self) -> Result<Self, InterpretErrorKind> {
+        finite(self * rhs)
+    }
+
+    #[inline]
+    fn div(&self, rhs: &Self) -> Result<Self, InterpretErrorKind> {
+        if *rhs == 0.0 {
+            return Err(InterpretErrorKind::DivisionByZero);
+        }
+        finite(self / rhs)
+    }
+
+    #[inline]
+    fn neg(&self) -> Result<Self, InterpretErrorKind> {
+        Ok(-self)
+    }
+
+    #[inline]
+    fn abs(&self) -> Result<Self, InterpretErrorKind> {
+        Ok(f64::abs(*self))
+    }
+
+    #[inline]
+    fn floor(&self) -> Self {
+        f64::floor(*self)
+    }
+
+    #[inline]
+    fn trunc(&self) -> Self {
+        f64::trunc(*self)
+    }
+
+    /// Real division, the quotient is never rounded.
+    #[inline]
+    fn div_with(
+        &self,
+        rhs: &Self,
+        _division: DivisionMode,
+        _overflow: OverflowMode,
+    ) -> Result<Self, InterpretErrorKind> {
+        self.div(rhs)
+    }
+
+    #[inline]
+    fn rem_with(&self, rhs: &Self, division: DivisionMode) -> Result<Self, InterpretErrorKind> {
+        if *rhs == 0.0 {
+            return Err(InterpretErrorKind::DivisionByZero);
+        }
+        finite(match division {
+            DivisionMode::Truncating => self % rhs,
+            DivisionMode::Floor => match self % rhs {
+                r if r != 0.0 && (r < 0.0) != (*rhs < 0.0) => r + rhs,
+                r => r,
+            },
+            DivisionMode::Euclidean => self.rem_euclid(*rhs),
+        })
+    }
+}
+
+/// Reports a result that is not a finite number as an error.
+#[inline]
+fn finite(value: f64) -> Result<f64, InterpretErrorKind> {
+    if value.is_nan() {
+        Err(InterpretErrorKind::NotANumber)
+    } else if value.is_infinite() {
+        Err(InterpretErrorKind::Infinity)
+    } else {
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bigint::BigInt;
+
+    #[test]
+    fn test_i64_arithmetic() {
+        assert_eq!(7.div(&2), Ok(3));
+        assert_eq!(7.div(&0), Err(InterpretErrorKind::DivisionByZero));
+        assert_eq!(i64::MIN.div(&-1), Err(InterpretErrorKind::Overflow));
+        assert_eq!(i64::MAX.add(&1), Err(InterpretErrorKind::Overflow));
+        assert_eq!(<i64 as Word>::parse("-12"), Some(-12));
+        assert_eq!(<i64 as Word>::parse("2.5"), None);
+    }
+
+    #[test]
+    fn test_integer_words() {
+        assert_eq!(i32::MAX.add(&1), Err(InterpretErrorKind::Overflow));
+        assert_eq!(i32::from_i64(i64::from(i32::MAX) + 1), None);
+        assert_eq!(
+            <i128 as Word>::parse("170141183460469231731687303715884105727"),
+            Some(i128::MAX)
+        );
+        assert_eq!(i128::MAX.to_i64(), None);
+        assert_eq!(3u64.sub(&4), Err(InterpretErrorKind::Overflow));
+        assert_eq!(3u64.neg(), Err(InterpretErrorKind::Overflow));
+        assert_eq!(0u64.neg(), Ok(0));
+        assert_eq!(3u64.abs(), Ok(3));
+        assert_eq!(u64::from_i64(-1), None);
+        assert_eq!(u64::MAX.to_i64(), None);
+        assert_eq!(Word::abs(&-5i32), Ok(5));
+    }
+
+    #[test]
+    fn test_overflow_modes() {
+        use OverflowMode::{Checked, Saturating, Wrapping};
+
+        assert_eq!(
+            i64::MAX.add_with(&1, Checked),
+            Err(InterpretErrorKind::Overflow)
+        );
+        assert_eq!(i64::MAX.add_with(&1, Wrapping), Ok(i64::MIN));
+        assert_eq!(i64::MAX.add_with(&1, Saturating), Ok(i64::MAX));
+        assert_eq!(i32::MIN.sub_with(&1, Saturating), Ok(i32::MIN));
+        assert_eq!(i32::MIN.mul_with(&-1, Wrapping), Ok(i32::MIN));
+        let truncating = DivisionMode::Truncating;
+        assert_eq!(i64::MIN.div_with(&-1, truncating, Saturating), Ok(i64::MAX));
+        assert_eq!(i64::MIN.div_with(&-1, truncating, Wrapping), Ok(i64::MIN));
+        assert_eq!(
+            1.div_with(&0, truncating, Wrapping),
+            Err(InterpretErrorKind::DivisionByZero)
+        );
+        assert_eq!(i64::MIN.neg_with(Saturating), Ok(i64::MAX));
+        assert_eq!(i64::MIN.abs_with(Wrapping), Ok(i64::MIN));
+        assert_eq!(3u64.neg_with(Saturating), Ok(0));
+        assert_eq!(3u64.neg_with(Wrapping), Ok(u64::MAX - 2));
+        assert_eq!(0u64.sub_with(&1, Saturating), Ok(0));
+        assert_eq!(
+            i8::from_u64_with(200, Checked),
+            Err(InterpretErrorKind::Overflow)
+        );
+        assert_eq!(i8::from_u64_with(200, Wrapping), Ok(-56));
+        assert_eq!(u8::from_u64_with(300, Saturating), Ok(u8::MAX));
+        assert_eq!(
+            f64::MAX.mul_with(&2.0, Saturating),
+            Err(InterpretErrorKind::Infinity)
+        );
+    }
+
+    #[test]
+    fn test_division_modes() {
+        use DivisionMode::{Euclidean, Floor, Truncating};
+        let checked = OverflowMode::Checked;
+        let cases = [(7, 2), (-7, 2), (7, -2), (-7, -2), (6, -3)];
+
+        let quotients = |mode| cases.map(|(a, b): (i64, i64)| a.div_with(&b, mode, checked));
+        let remainders = |mode| cases.map(|(a, b): (i64, i64)| a.rem_with(&b, mode));
+        assert_eq!(quotients(Truncating), [3, -3, -3, 3, -2].map(Ok));
+        assert_eq!(remainders(Truncating), [1, -1, 1, -1, 0].map(Ok));
+        assert_eq!(quotients(Floor), [3, -4, -4, 3, -2].map(Ok));
+        assert_eq!(remainders(Floor), [1, 1, -1, -1, 0].map(Ok));
+        assert_eq!(quotients(Euclidean), [3, -4, -3, 4, -2].map(Ok));
+        assert_eq!(remainders(Euclidean), [1, 1, 1, 1, 0].map(Ok));
+
+        // Words without native support use the generic implementation.
+        let big = |(a, b): (i64, i64)| (BigInt::from(a), BigInt::from(b));
+        for mode in [Truncating, Floor, Euclidean] {
+            for (case, quotient) in cases.iter().zip(quotients(mode)) {
+                let (a, b) = big(*case);
+                assert_eq!(a.div_with(&b, mode, checked), quotient.map(BigInt::from));
+            }
+            for (case, remainder) in cases.iter().zip(remainders(mode)) {
+                let (a, b) = big(*case);
+                assert_eq!(a.rem_with(&b, mode), remainder.map(BigInt::from));
+            }
+        }
+
+        assert_eq!(
+            i64::MIN.div_with(&-1, Floor, checked),
+            Err(InterpretErrorKind::Overflow)
+        );
+        assert_eq!(i64::MIN.rem_with(&-1, Euclidean), Ok(0));
+        assert_eq!(
+            7.rem_with(&0, Floor),
+            Err(InterpretErrorKind::DivisionByZero)
+        );
+        assert_eq!((-7.5).rem_with(&2.0, Floor), Ok(0.5));
+        assert_eq!(7u64.div_with(&2, Floor, checked), Ok(3));
+    }
+
+    #[test]
+    fn test_f64_arithmetic() {
+        assert_eq!(7.0.div(&2.0), Ok(3.5));
+        assert_eq!(1.0.div(&0.0), Err(InterpretErrorKind::DivisionByZero));
+        assert_eq!(f64::MAX.mul(&2.0), Err(InterpretErrorKind::Infinity));
+        assert_eq!(Word::floor(&-2.5), -3.0);
+        assert_eq!(Word::trunc(&-2.5), -2.0);
+        assert_eq!(<f64 as Word>::parse("2.5"), Some(2.5));
+        assert_eq!(<f64 as Word>::parse("NaN"), None);
+    }
+
+    #[test]
+    fn test_f64_conversions() {
+        assert_eq!(3.0.to_index(), Some(3));
+        assert_eq!(2.5.to_index(), None);
+        assert_eq!((-1.0).to_index(), None);
+        assert_eq!(f64::from_i64(i64::MAX), None);
+        assert_eq!(f64::from_i64(-5), Some(-5.0));
+    }
+}